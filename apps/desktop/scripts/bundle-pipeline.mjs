@@ -1,4 +1,5 @@
 import { build } from 'esbuild';
+import { execFileSync } from 'node:child_process';
 import fs from 'node:fs/promises';
 import path from 'node:path';
 import { fileURLToPath } from 'node:url';
@@ -8,11 +9,28 @@ const __dirname = path.dirname(__filename);
 const appRoot = path.resolve(__dirname, '..');
 const repoRoot = path.resolve(appRoot, '..', '..');
 const resourcesDir = path.resolve(appRoot, 'src-tauri', 'resources');
-const bundledNodePath = path.join(resourcesDir, 'bin', 'node');
+const binariesDir = path.resolve(appRoot, 'src-tauri', 'binaries');
 const entryFile = path.resolve(repoRoot, 'scripts', 'pipeline.js');
 const outFile = path.join(resourcesDir, 'pipeline.bundle.mjs');
 
-await fs.mkdir(path.dirname(bundledNodePath), { recursive: true });
+// Tauri looks up externalBin sidecars by target triple; the tauri CLI exports it to before* hooks.
+function targetTriple() {
+  if (process.env.TAURI_ENV_TARGET_TRIPLE) {
+    return process.env.TAURI_ENV_TARGET_TRIPLE;
+  }
+  const rustcInfo = execFileSync('rustc', ['-vV'], { encoding: 'utf8' });
+  const host = rustcInfo.match(/^host: (\S+)$/m);
+  if (!host) {
+    throw new Error('failed to determine target triple from `rustc -vV`');
+  }
+  return host[1];
+}
+
+const triple = targetTriple();
+const extension = triple.includes('windows') ? '.exe' : '';
+const sidecarNodePath = path.join(binariesDir, `pipeline-node-${triple}${extension}`);
+
+await fs.mkdir(binariesDir, { recursive: true });
 await fs.mkdir(path.dirname(outFile), { recursive: true });
 
 await build({
@@ -27,8 +45,8 @@ await build({
   external: ['playwright']
 });
 
-await fs.copyFile(process.execPath, bundledNodePath);
-await fs.chmod(bundledNodePath, 0o755);
+await fs.copyFile(process.execPath, sidecarNodePath);
+await fs.chmod(sidecarNodePath, 0o755);
 
 console.log(`Bundled pipeline: ${outFile}`);
-console.log(`Bundled node runtime: ${bundledNodePath}`);
+console.log(`Bundled node sidecar: ${sidecarNodePath}`);
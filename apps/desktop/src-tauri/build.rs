@@ -1,3 +1,29 @@
+use std::path::Path;
+use std::process::Command;
+
 fn main() {
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|commit| !commit.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=DMA_GIT_COMMIT={}", commit);
+
+    let git_dir = Path::new("../../../.git");
+    let git_head = git_dir.join("HEAD");
+    if git_head.exists() {
+        println!("cargo:rerun-if-changed={}", git_head.display());
+        let head = std::fs::read_to_string(&git_head).unwrap_or_default();
+        if let Some(reference) = head.trim().strip_prefix("ref: ") {
+            println!(
+                "cargo:rerun-if-changed={}",
+                git_dir.join(reference).display()
+            );
+        }
+    }
+
     tauri_build::build()
 }
@@ -0,0 +1,108 @@
+use serde::Serialize;
+use std::fs;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::CompressionMethod;
+
+#[derive(Serialize)]
+pub struct ZipExportResult {
+    path: String,
+    files: usize,
+    bytes: u64,
+}
+
+fn compression_method(value: Option<&str>) -> Result<CompressionMethod, String> {
+    match value
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("") | Some("store") => Ok(CompressionMethod::Stored),
+        Some("deflate") => Ok(CompressionMethod::Deflated),
+        #[cfg(feature = "zstd")]
+        Some("zstd") => Ok(CompressionMethod::Zstd),
+        #[cfg(not(feature = "zstd"))]
+        Some("zstd") => Err("zstd compression is not enabled in this build".to_string()),
+        Some(other) => Err(format!("unsupported compression: {}", other)),
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|error| format!("failed to read dir {}: {}", dir.display(), error))?;
+    for entry in entries {
+        let entry = entry.map_err(|error| format!("failed to read dir entry: {}", error))?;
+        let path = entry.path();
+        let file_type = entry
+            .file_type()
+            .map_err(|error| format!("failed to stat {}: {}", path.display(), error))?;
+        if file_type.is_dir() {
+            collect_files(&path, files)?;
+        } else if file_type.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn export_zip(
+    app: tauri::AppHandle,
+    src_dir: String,
+    dst: String,
+    compression: Option<String>,
+    level: Option<i64>,
+) -> Result<ZipExportResult, String> {
+    let method = compression_method(compression.as_deref())?;
+    let source = crate::resolve_scoped_path(&app, &src_dir)?;
+    let destination = crate::resolve_scoped_path(&app, &dst)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut files = Vec::new();
+        collect_files(&source, &mut files)?;
+        files.retain(|file| file != &destination);
+        files.sort();
+
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+        }
+        let output = fs::File::create(&destination)
+            .map_err(|error| format!("failed to create {}: {}", destination.display(), error))?;
+
+        let level = if method == CompressionMethod::Stored {
+            None
+        } else {
+            level
+        };
+        let options = SimpleFileOptions::default()
+            .compression_method(method)
+            .compression_level(level)
+            .large_file(true);
+        let mut writer = zip::ZipWriter::new(BufWriter::new(output));
+        for file in &files {
+            let entry_name = crate::to_display_path(file, &source);
+            writer
+                .start_file(entry_name.as_str(), options)
+                .map_err(|error| format!("failed to add {}: {}", entry_name, error))?;
+            let mut input = fs::File::open(file)
+                .map_err(|error| format!("failed to open {}: {}", file.display(), error))?;
+            io::copy(&mut input, &mut writer)
+                .map_err(|error| format!("failed to write {}: {}", entry_name, error))?;
+        }
+        writer
+            .finish()
+            .map_err(|error| format!("failed to finish {}: {}", destination.display(), error))?;
+
+        let bytes = fs::metadata(&destination)
+            .map_err(|error| format!("failed to stat {}: {}", destination.display(), error))?
+            .len();
+        Ok(ZipExportResult {
+            path: destination.to_string_lossy().replace('\\', "/"),
+            files: files.len(),
+            bytes,
+        })
+    })
+    .await
+    .map_err(|error| format!("failed to join export_zip task: {}", error))?
+}
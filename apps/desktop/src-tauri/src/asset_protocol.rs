@@ -0,0 +1,177 @@
+use percent_encoding::percent_decode_str;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{UriSchemeContext, UriSchemeResponder};
+
+pub const ASSET_SCHEME: &str = "dma-asset";
+// The responder only accepts a complete body, so large files go out in windows: ranged replies are
+// capped, and an unranged request for anything bigger than an image gets the first window as a
+// 206 that media elements continue with Range requests.
+const MAX_RANGE_BYTES: u64 = 8 * 1024 * 1024;
+const MAX_UNRANGED_BYTES: u64 = 64 * 1024 * 1024;
+
+fn error_response(status: StatusCode, message: String) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(message.into_bytes())
+        .unwrap_or_default()
+}
+
+// Only single ranges are supported; that is all media elements and image decoders ask for.
+fn parse_byte_range(value: &str, total_size: u64) -> Option<(u64, u64)> {
+    let (start, end) = value.trim().strip_prefix("bytes=")?.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let suffix = suffix.parse::<u64>().ok()?.min(total_size);
+            (total_size - suffix, total_size.checked_sub(1)?)
+        }
+        (start, "") => (start.parse().ok()?, total_size.checked_sub(1)?),
+        (start, end) => (
+            start.parse().ok()?,
+            end.parse::<u64>().ok()?.min(total_size.checked_sub(1)?),
+        ),
+    };
+    (start <= end && start < total_size).then_some((start, end))
+}
+
+// Paths are project-relative (`dma-asset://localhost/examples/shot.png`); absolute paths keep
+// their own leading slash after the host (`dma-asset://localhost//Users/me/shot.png`).
+fn requested_path(request: &Request<Vec<u8>>) -> Result<PathBuf, String> {
+    let raw = request.uri().path();
+    let decoded = percent_decode_str(raw.strip_prefix('/').unwrap_or(raw))
+        .decode_utf8()
+        .map_err(|error| format!("invalid asset path: {}", error))?;
+    Ok(crate::resolve_project_path(&decoded))
+}
+
+const APP_ORIGINS: [&str; 3] = [
+    "tauri://localhost",
+    "http://tauri.localhost",
+    "https://tauri.localhost",
+];
+
+// Only the app's own pages (plus the dev server in debug builds) may read assets cross-origin.
+fn app_origin(app: &tauri::AppHandle, request: &Request<Vec<u8>>) -> Option<String> {
+    let origin = request.headers().get(header::ORIGIN)?.to_str().ok()?;
+    let dev_origin = app
+        .config()
+        .build
+        .dev_url
+        .as_ref()
+        .filter(|_| cfg!(debug_assertions))
+        .map(|url| url.origin().ascii_serialization());
+    (APP_ORIGINS.contains(&origin) || dev_origin.as_deref() == Some(origin))
+        .then(|| origin.to_string())
+}
+
+fn serve_asset(app: &tauri::AppHandle, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let origin = app_origin(app, request);
+    if origin.is_none() && request.headers().contains_key(header::ORIGIN) {
+        return error_response(
+            StatusCode::FORBIDDEN,
+            "assets are only served to the app".to_string(),
+        );
+    }
+    let path = match requested_path(request) {
+        Ok(path) => path,
+        Err(message) => return error_response(StatusCode::BAD_REQUEST, message),
+    };
+    let Some(path) = crate::scoped_target(&path, &crate::allowed_roots(app)) else {
+        return error_response(
+            StatusCode::FORBIDDEN,
+            format!("path is outside the allowed roots: {}", path.display()),
+        );
+    };
+
+    let mut file = match fs::File::open(&path) {
+        Ok(file) => file,
+        Err(error) => {
+            return error_response(
+                StatusCode::NOT_FOUND,
+                format!("failed to open {}: {}", path.display(), error),
+            )
+        }
+    };
+    let total_size = match file.metadata() {
+        Ok(metadata) if metadata.is_file() => metadata.len(),
+        _ => {
+            return error_response(
+                StatusCode::NOT_FOUND,
+                format!("not a file: {}", path.display()),
+            )
+        }
+    };
+    let content_type = match crate::read_sniff_head(&path) {
+        Ok(head) => crate::sniff_mime(&head),
+        Err(message) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, message),
+    };
+
+    let range = request
+        .headers()
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok());
+    let (status, start, end) = match range {
+        Some(range) => match parse_byte_range(range, total_size) {
+            Some((start, end)) => (
+                StatusCode::PARTIAL_CONTENT,
+                start,
+                end.min(start + MAX_RANGE_BYTES - 1),
+            ),
+            None => {
+                return Response::builder()
+                    .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                    .header(header::CONTENT_RANGE, format!("bytes */{}", total_size))
+                    .body(Vec::new())
+                    .unwrap_or_default()
+            }
+        },
+        None if total_size > MAX_UNRANGED_BYTES => {
+            (StatusCode::PARTIAL_CONTENT, 0, MAX_RANGE_BYTES - 1)
+        }
+        None => (StatusCode::OK, 0, total_size.saturating_sub(1)),
+    };
+
+    let length = if total_size == 0 { 0 } else { end - start + 1 };
+    let mut bytes = Vec::with_capacity(length as usize);
+    let read = file
+        .seek(SeekFrom::Start(start))
+        .and_then(|_| (&mut file).take(length).read_to_end(&mut bytes));
+    if let Err(error) = read {
+        return error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("failed to read {}: {}", path.display(), error),
+        );
+    }
+
+    let mut response = Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::CACHE_CONTROL, "no-cache")
+        .header(header::VARY, "Origin");
+    if let Some(origin) = origin {
+        response = response.header(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+    }
+    if status == StatusCode::PARTIAL_CONTENT {
+        response = response.header(
+            header::CONTENT_RANGE,
+            format!("bytes {}-{}/{}", start, end, total_size),
+        );
+    }
+    response.body(bytes).unwrap_or_default()
+}
+
+pub fn handle_asset_request(
+    context: UriSchemeContext<'_, tauri::Wry>,
+    request: Request<Vec<u8>>,
+    responder: UriSchemeResponder,
+) {
+    let app = context.app_handle().clone();
+    // Large screenshots would otherwise stall the webview's IPC thread while they are read.
+    tauri::async_runtime::spawn_blocking(move || {
+        responder.respond(serve_asset(&app, &request));
+    });
+}
@@ -0,0 +1,521 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::CompressionMethod;
+
+const BUNDLE_MANIFEST_FILE: &str = "bundle-manifest.json";
+const BUNDLE_VERSION: u64 = 1;
+const BUNDLE_ASSETS_DIR: &str = "assets";
+
+#[derive(Serialize, Deserialize)]
+struct BundleManifest {
+    bundle_version: u64,
+    project: String,
+    assets: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct BundleExportResult {
+    path: String,
+    included: Vec<String>,
+    missing: Vec<String>,
+    refused: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct BundleImportResult {
+    project_path: String,
+    skipped: Vec<String>,
+}
+
+// Project files reference images and fonts through keys such as `sourceImagePath` and `imagePath`.
+fn is_asset_key(key: &str) -> bool {
+    key.to_ascii_lowercase().ends_with("path")
+}
+
+fn is_local_reference(value: &str) -> bool {
+    let trimmed = value.trim();
+    !trimmed.is_empty() && !trimmed.contains("://") && !trimmed.starts_with("data:")
+}
+
+pub(crate) fn visit_asset_references(value: &mut Value, visit: &mut impl FnMut(&mut String)) {
+    match value {
+        Value::Object(object) => {
+            for (key, child) in object.iter_mut() {
+                match child {
+                    Value::String(reference) if is_asset_key(key) => {
+                        if is_local_reference(reference) {
+                            visit(reference);
+                        }
+                    }
+                    _ => visit_asset_references(child, visit),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                visit_asset_references(item, visit);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn write_entry(
+    writer: &mut zip::ZipWriter<BufWriter<fs::File>>,
+    name: &str,
+    bytes: &[u8],
+    options: SimpleFileOptions,
+) -> Result<(), String> {
+    writer
+        .start_file(name, options)
+        .map_err(|error| format!("failed to add {}: {}", name, error))?;
+    writer
+        .write_all(bytes)
+        .map_err(|error| format!("failed to write {}: {}", name, error))
+}
+
+fn write_bundle(
+    project: PathBuf,
+    destination: PathBuf,
+    roots: &[PathBuf],
+) -> Result<BundleExportResult, String> {
+    let project_name = project
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("invalid project path: {}", project.display()))?;
+    let raw = crate::load_text_file(&project, None, None)?;
+    let mut document: Value = serde_json::from_str(&raw)
+        .map_err(|error| format!("failed to parse {}: {}", project.display(), error))?;
+
+    let mut references = Vec::new();
+    visit_asset_references(&mut document, &mut |reference| {
+        if !references.contains(reference) {
+            references.push(reference.clone());
+        }
+    });
+
+    // References are relative to the project file, matching how the pipeline resolves them.
+    let project_dir = project.parent().unwrap_or(Path::new(""));
+    let mut bundled = HashMap::new();
+    let mut sources = HashMap::<PathBuf, String>::new();
+    let mut used_names = HashSet::new();
+    let mut assets = Vec::new();
+    let mut included = Vec::new();
+    let mut missing = Vec::new();
+    let mut refused = Vec::new();
+    for reference in references {
+        let source = crate::resolve_with_root(project_dir, reference.trim());
+        // Absolute and `..` references could otherwise pull any readable file into a shareable zip.
+        let Some(source) = crate::scoped_target(&source, roots) else {
+            refused.push(reference);
+            continue;
+        };
+        if !source.is_file() {
+            missing.push(reference);
+            continue;
+        }
+        if let Some(existing) = sources.get(&source) {
+            bundled.insert(reference.clone(), existing.clone());
+            included.push(reference);
+            continue;
+        }
+
+        let file_name = source.file_name().unwrap_or(OsStr::new("asset"));
+        let entry_name = (1..)
+            .map(|attempt| {
+                format!(
+                    "{}/{}",
+                    BUNDLE_ASSETS_DIR,
+                    crate::numbered_file_name(file_name, attempt).to_string_lossy()
+                )
+            })
+            .find(|candidate| used_names.insert(candidate.to_lowercase()))
+            .unwrap_or_default();
+        sources.insert(source.clone(), entry_name.clone());
+        bundled.insert(reference.clone(), entry_name.clone());
+        assets.push((source, entry_name));
+        included.push(reference);
+    }
+
+    visit_asset_references(&mut document, &mut |reference| {
+        if let Some(entry_name) = bundled.get(reference.as_str()) {
+            *reference = entry_name.clone();
+        }
+    });
+    let manifest = BundleManifest {
+        bundle_version: BUNDLE_VERSION,
+        project: project_name.clone(),
+        assets: assets
+            .iter()
+            .map(|(_, entry_name)| entry_name.clone())
+            .collect(),
+    };
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+    }
+    let output = fs::File::create(&destination)
+        .map_err(|error| format!("failed to create {}: {}", destination.display(), error))?;
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+    let mut writer = zip::ZipWriter::new(BufWriter::new(output));
+
+    let rewritten = serde_json::to_string_pretty(&document)
+        .map_err(|error| format!("failed to serialize project: {}", error))?;
+    write_entry(&mut writer, &project_name, rewritten.as_bytes(), options)?;
+    let manifest = serde_json::to_string_pretty(&manifest)
+        .map_err(|error| format!("failed to serialize bundle manifest: {}", error))?;
+    write_entry(
+        &mut writer,
+        BUNDLE_MANIFEST_FILE,
+        manifest.as_bytes(),
+        options,
+    )?;
+
+    for (source, entry_name) in &assets {
+        writer
+            .start_file(entry_name.as_str(), options)
+            .map_err(|error| format!("failed to add {}: {}", entry_name, error))?;
+        let mut input = fs::File::open(source)
+            .map_err(|error| format!("failed to open {}: {}", source.display(), error))?;
+        io::copy(&mut input, &mut writer)
+            .map_err(|error| format!("failed to write {}: {}", entry_name, error))?;
+    }
+    writer
+        .finish()
+        .map_err(|error| format!("failed to finish {}: {}", destination.display(), error))?;
+
+    Ok(BundleExportResult {
+        path: destination.to_string_lossy().replace('\\', "/"),
+        included,
+        missing,
+        refused,
+    })
+}
+
+#[tauri::command]
+pub async fn export_bundle(
+    app: tauri::AppHandle,
+    project_path: String,
+    dst_zip: String,
+) -> Result<BundleExportResult, String> {
+    let project = crate::resolve_scoped_path(&app, project_path.trim())?;
+    let destination = crate::resolve_scoped_path(&app, dst_zip.trim())?;
+    let roots = crate::allowed_roots(&app);
+    tauri::async_runtime::spawn_blocking(move || write_bundle(project, destination, &roots))
+        .await
+        .map_err(|error| format!("failed to join export_bundle task: {}", error))?
+}
+
+fn is_plain_file_name(name: &str) -> bool {
+    !name.is_empty()
+        && Path::new(name)
+            .file_name()
+            .map(|file_name| file_name == name)
+            .unwrap_or(false)
+}
+
+fn read_manifest(
+    archive: &mut zip::ZipArchive<BufReader<fs::File>>,
+) -> Result<BundleManifest, String> {
+    let mut raw = String::new();
+    archive
+        .by_name(BUNDLE_MANIFEST_FILE)
+        .map_err(|error| format!("failed to find {}: {}", BUNDLE_MANIFEST_FILE, error))?
+        .read_to_string(&mut raw)
+        .map_err(|error| format!("failed to read {}: {}", BUNDLE_MANIFEST_FILE, error))?;
+    let manifest: BundleManifest = serde_json::from_str(&raw)
+        .map_err(|error| format!("failed to parse {}: {}", BUNDLE_MANIFEST_FILE, error))?;
+
+    if manifest.bundle_version != BUNDLE_VERSION {
+        return Err(format!(
+            "unsupported bundle version: {}",
+            manifest.bundle_version
+        ));
+    }
+    if !is_plain_file_name(&manifest.project) {
+        return Err(format!("invalid bundle project name: {}", manifest.project));
+    }
+    let entries = archive.file_names().collect::<HashSet<&str>>();
+    for name in std::iter::once(&manifest.project).chain(&manifest.assets) {
+        if !entries.contains(name.as_str()) {
+            return Err(format!("bundle is missing {}", name));
+        }
+    }
+    Ok(manifest)
+}
+
+fn extract_bundle(source: PathBuf, destination: PathBuf) -> Result<BundleImportResult, String> {
+    let input = fs::File::open(&source)
+        .map_err(|error| format!("failed to open {}: {}", source.display(), error))?;
+    let mut archive = zip::ZipArchive::new(BufReader::new(input))
+        .map_err(|error| format!("failed to read {}: {}", source.display(), error))?;
+    let manifest = read_manifest(&mut archive)?;
+
+    fs::create_dir_all(&destination)
+        .map_err(|error| format!("failed to create {}: {}", destination.display(), error))?;
+    let destination = fs::canonicalize(&destination)
+        .map_err(|error| format!("failed to resolve {}: {}", destination.display(), error))?;
+    let project = destination.join(&manifest.project);
+
+    // A file already sitting where an entry would land would be picked up by the project's
+    // bundle-relative references, so the import refuses to start rather than mixing them in.
+    for index in 0..archive.len() {
+        let entry = archive
+            .by_index(index)
+            .map_err(|error| format!("failed to read bundle entry {}: {}", index, error))?;
+        if let Some(relative) = entry.enclosed_name().filter(|_| !entry.is_dir()) {
+            let target = destination.join(relative);
+            if fs::symlink_metadata(&target).is_ok() {
+                return Err(format!("{} already exists", target.display()));
+            }
+        }
+    }
+
+    let mut extracted = HashSet::new();
+    let mut skipped = Vec::new();
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|error| format!("failed to read bundle entry {}: {}", index, error))?;
+        let name = entry.name().to_string();
+        // enclosed_name rejects absolute paths and `..` components, resolving the deepest existing
+        // ancestor catches symlinked directories in dest_dir before anything is created, and
+        // create_new refuses to follow or replace whatever appeared at the target since.
+        let Some(relative) = entry.enclosed_name().filter(|_| !entry.is_symlink()) else {
+            skipped.push(name);
+            continue;
+        };
+        let target = destination.join(&relative);
+        let contained = crate::canonicalize_existing_prefix(&target)
+            .map(|resolved| resolved.starts_with(&destination))
+            .unwrap_or(false);
+        if !contained {
+            skipped.push(name);
+            continue;
+        }
+        if entry.is_dir() {
+            fs::create_dir_all(&target)
+                .map_err(|error| format!("failed to create {}: {}", target.display(), error))?;
+            continue;
+        }
+
+        let parent = target.parent().unwrap_or(&destination);
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create {}: {}", parent.display(), error))?;
+        let mut output = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&target)
+            .map_err(|error| format!("failed to create {}: {}", target.display(), error))?;
+        io::copy(&mut entry, &mut output)
+            .map_err(|error| format!("failed to extract {}: {}", name, error))?;
+        extracted.insert(name);
+    }
+
+    if !extracted.contains(&manifest.project) {
+        return Err(format!(
+            "bundle project was not extracted: {}",
+            manifest.project
+        ));
+    }
+
+    // The project sits next to `assets/`, so its bundle-relative references already resolve.
+    Ok(BundleImportResult {
+        project_path: crate::to_display_path(&project, &crate::project_root()),
+        skipped,
+    })
+}
+
+#[tauri::command]
+pub async fn import_bundle(
+    app: tauri::AppHandle,
+    zip_path: String,
+    dest_dir: String,
+) -> Result<BundleImportResult, String> {
+    let source = crate::resolve_scoped_path(&app, zip_path.trim())?;
+    let destination = crate::resolve_scoped_path(&app, dest_dir.trim())?;
+    tauri::async_runtime::spawn_blocking(move || extract_bundle(source, destination))
+        .await
+        .map_err(|error| format!("failed to join import_bundle task: {}", error))?
+}
+
+// Project archives use the bundle format, so zips from either pair of commands are interchangeable.
+#[tauri::command]
+pub async fn export_project_archive(
+    app: tauri::AppHandle,
+    project_path: String,
+    output_zip: String,
+) -> Result<BundleExportResult, String> {
+    export_bundle(app, project_path, output_zip).await
+}
+
+#[tauri::command]
+pub async fn import_project_archive(
+    app: tauri::AppHandle,
+    zip_path: String,
+    destination: String,
+) -> Result<BundleImportResult, String> {
+    import_bundle(app, zip_path, destination).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dma-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn bundle_export_resolves_assets_relative_to_the_project_file() {
+        let dir = scratch_dir("bundle-export");
+        fs::create_dir_all(dir.join("project/shots")).unwrap();
+        fs::write(dir.join("project/shots/home.png"), b"png-bytes").unwrap();
+        let project = dir.join("project/app.dma.json");
+        fs::write(
+            &project,
+            r#"{"screens":[{"sourceImagePath":"shots/home.png"},{"imagePath":"missing.png"}]}"#,
+        )
+        .unwrap();
+
+        let zip_path = dir.join("app.zip");
+        write_bundle(project, zip_path.clone(), std::slice::from_ref(&dir)).unwrap();
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&zip_path).unwrap()).unwrap();
+        assert!(archive.by_name("assets/home.png").is_ok());
+        let mut raw = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("app.dma.json").unwrap(), &mut raw)
+            .unwrap();
+        let document: serde_json::Value = serde_json::from_str(&raw).unwrap();
+        assert_eq!(document["screens"][0]["sourceImagePath"], "assets/home.png");
+        assert_eq!(document["screens"][1]["imagePath"], "missing.png");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn bundle_export_refuses_references_outside_the_allowed_roots() {
+        let dir = scratch_dir("bundle-refused");
+        fs::create_dir_all(dir.join("project/shots")).unwrap();
+        fs::write(dir.join("project/shots/home.png"), b"png-bytes").unwrap();
+        let secret = dir.join("secret.env");
+        fs::write(&secret, b"TOKEN=1").unwrap();
+        let absolute = secret.to_string_lossy().replace('\\', "/");
+        let project = dir.join("project/app.dma.json");
+        let document = serde_json::json!({
+            "screens": [
+                { "sourceImagePath": "shots/home.png" },
+                { "sourceImagePath": absolute },
+                { "imagePath": "../secret.env" },
+            ]
+        });
+        fs::write(&project, document.to_string()).unwrap();
+
+        let zip_path = dir.join("out/app.zip");
+        let result = write_bundle(project, zip_path.clone(), &[dir.join("project")]).unwrap();
+        assert_eq!(result.included, vec!["shots/home.png".to_string()]);
+        assert_eq!(result.refused, vec![absolute, "../secret.env".to_string()]);
+        assert!(result.missing.is_empty());
+
+        let archive = zip::ZipArchive::new(fs::File::open(&zip_path).unwrap()).unwrap();
+        let names = archive.file_names().collect::<Vec<&str>>();
+        assert!(names.iter().all(|name| !name.contains("secret")));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn bundle_import_does_not_write_through_existing_symlinks() {
+        let dir = scratch_dir("bundle-symlink");
+        fs::create_dir_all(dir.join("project")).unwrap();
+        fs::write(dir.join("project/home.png"), b"bundled").unwrap();
+        let project = dir.join("project/app.dma.json");
+        fs::write(&project, r#"{"sourceImagePath":"home.png"}"#).unwrap();
+        let zip_path = dir.join("app.zip");
+        write_bundle(project, zip_path.clone(), std::slice::from_ref(&dir)).unwrap();
+
+        let outside = dir.join("outside.png");
+        fs::write(&outside, b"untouched").unwrap();
+        fs::create_dir_all(dir.join("imported/assets")).unwrap();
+        std::os::unix::fs::symlink(&outside, dir.join("imported/assets/home.png")).unwrap();
+
+        assert!(extract_bundle(zip_path.clone(), dir.join("imported")).is_err());
+        assert_eq!(fs::read(&outside).unwrap(), b"untouched");
+        assert!(!dir.join("imported/app.dma.json").exists());
+
+        fs::create_dir_all(dir.join("elsewhere")).unwrap();
+        fs::create_dir_all(dir.join("linked")).unwrap();
+        std::os::unix::fs::symlink(dir.join("elsewhere"), dir.join("linked/assets")).unwrap();
+        extract_bundle(zip_path, dir.join("linked")).unwrap();
+        assert!(dir.join("linked/app.dma.json").is_file());
+        assert_eq!(fs::read_dir(dir.join("elsewhere")).unwrap().count(), 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn bundle_import_refuses_to_mix_with_existing_assets() {
+        let dir = scratch_dir("bundle-conflict");
+        fs::create_dir_all(dir.join("project")).unwrap();
+        fs::write(dir.join("project/home.png"), b"bundled").unwrap();
+        let project = dir.join("project/app.dma.json");
+        fs::write(&project, r#"{"sourceImagePath":"home.png"}"#).unwrap();
+        let zip_path = dir.join("app.zip");
+        write_bundle(project, zip_path.clone(), std::slice::from_ref(&dir)).unwrap();
+
+        fs::create_dir_all(dir.join("imported/assets")).unwrap();
+        fs::write(dir.join("imported/assets/home.png"), b"stale").unwrap();
+        assert!(extract_bundle(zip_path, dir.join("imported")).is_err());
+        assert!(!dir.join("imported/app.dma.json").exists());
+        assert_eq!(
+            fs::read(dir.join("imported/assets/home.png")).unwrap(),
+            b"stale"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn project_archive_round_trip_keeps_asset_references_resolvable() {
+        let dir = scratch_dir("archive-round-trip");
+        fs::create_dir_all(dir.join("source/shots/ios")).unwrap();
+        fs::create_dir_all(dir.join("source/shots/android")).unwrap();
+        fs::write(dir.join("source/shots/ios/home.png"), b"ios").unwrap();
+        fs::write(dir.join("source/shots/android/home.png"), b"android").unwrap();
+        let project = dir.join("source/app.dma.json");
+        fs::write(
+            &project,
+            r#"{"screens":[{"sourceImagePath":"shots/ios/home.png"},{"sourceImagePath":"shots/android/home.png"}]}"#,
+        )
+        .unwrap();
+
+        let zip_path = dir.join("app.zip");
+        write_bundle(project, zip_path.clone(), std::slice::from_ref(&dir)).unwrap();
+        extract_bundle(zip_path, dir.join("imported/nested")).unwrap();
+
+        let extracted = dir.join("imported/nested/app.dma.json");
+        let document: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&extracted).unwrap()).unwrap();
+        let extracted_dir = extracted.parent().unwrap();
+        let contents = document["screens"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|screen| {
+                let reference = screen["sourceImagePath"].as_str().unwrap();
+                assert!(Path::new(reference).is_relative());
+                fs::read(crate::resolve_with_root(extracted_dir, reference)).unwrap()
+            })
+            .collect::<Vec<Vec<u8>>>();
+        assert_eq!(contents, vec![b"ios".to_vec(), b"android".to_vec()]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,110 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+const MAX_CHUNK_BYTES: usize = 8 * 1024 * 1024;
+
+struct OpenRead {
+    path: PathBuf,
+    file: fs::File,
+    position: u64,
+}
+
+#[derive(Default)]
+pub struct ChunkedReads {
+    next_handle: AtomicU64,
+    open: Mutex<HashMap<u64, OpenRead>>,
+}
+
+#[derive(Serialize)]
+pub struct FileStream {
+    handle: u64,
+    total_size: u64,
+}
+
+#[derive(Serialize)]
+pub struct FileChunk {
+    data: String,
+    offset: u64,
+    eof: bool,
+}
+
+#[tauri::command]
+pub fn open_file_stream(
+    app: tauri::AppHandle,
+    reads: tauri::State<'_, ChunkedReads>,
+    path: String,
+    file_id: Option<String>,
+) -> Result<FileStream, String> {
+    let path = match file_id {
+        Some(id) => crate::resolve_file_id(&id)?,
+        None => crate::resolve_project_path(&path),
+    };
+    crate::ensure_in_scope(&app, &path)?;
+    let file = fs::File::open(&path)
+        .map_err(|error| format!("failed to open {}: {}", path.display(), error))?;
+    let total_size = file
+        .metadata()
+        .map_err(|error| format!("failed to stat {}: {}", path.display(), error))?
+        .len();
+
+    let handle = reads.next_handle.fetch_add(1, Ordering::Relaxed) + 1;
+    reads
+        .open
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(
+            handle,
+            OpenRead {
+                path,
+                file,
+                position: 0,
+            },
+        );
+    Ok(FileStream { handle, total_size })
+}
+
+#[tauri::command]
+pub fn read_chunk(
+    reads: tauri::State<'_, ChunkedReads>,
+    handle: u64,
+    max_bytes: usize,
+) -> Result<FileChunk, String> {
+    let mut open = reads
+        .open
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let read = open
+        .get_mut(&handle)
+        .ok_or_else(|| format!("unknown file stream handle: {}", handle))?;
+
+    let limit = max_bytes.clamp(1, MAX_CHUNK_BYTES);
+    let mut bytes = Vec::with_capacity(limit);
+    (&mut read.file)
+        .take(limit as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|error| format!("failed to read {}: {}", read.path.display(), error))?;
+
+    let offset = read.position;
+    read.position += bytes.len() as u64;
+    Ok(FileChunk {
+        data: STANDARD.encode(&bytes),
+        offset,
+        eof: bytes.len() < limit,
+    })
+}
+
+#[tauri::command]
+pub fn close_stream(reads: tauri::State<'_, ChunkedReads>, handle: u64) -> bool {
+    reads
+        .open
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(&handle)
+        .is_some()
+}
@@ -0,0 +1,122 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+struct PendingWrite {
+    target: PathBuf,
+    temp_path: PathBuf,
+    file: fs::File,
+}
+
+#[derive(Default)]
+pub struct ChunkedWrites {
+    next_handle: AtomicU64,
+    pending: Mutex<HashMap<u64, PendingWrite>>,
+}
+
+impl ChunkedWrites {
+    fn take(&self, handle: u64) -> Result<PendingWrite, String> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&handle)
+            .ok_or_else(|| format!("unknown file write handle: {}", handle))
+    }
+}
+
+#[tauri::command]
+pub fn begin_file_write(
+    app: tauri::AppHandle,
+    writes: tauri::State<'_, ChunkedWrites>,
+    path: String,
+) -> Result<u64, String> {
+    let target = crate::resolve_scoped_path(&app, &path)?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+    }
+
+    let temp_path = crate::atomic_temp_path(&target)?;
+    let file = fs::File::create(&temp_path)
+        .map_err(|error| format!("failed to create {}: {}", temp_path.display(), error))?;
+
+    let handle = writes.next_handle.fetch_add(1, Ordering::Relaxed) + 1;
+    writes
+        .pending
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(
+            handle,
+            PendingWrite {
+                target,
+                temp_path,
+                file,
+            },
+        );
+    Ok(handle)
+}
+
+#[tauri::command]
+pub fn append_file_chunk(
+    writes: tauri::State<'_, ChunkedWrites>,
+    handle: u64,
+    data_base64: String,
+) -> Result<(), String> {
+    let bytes = STANDARD
+        .decode(data_base64.as_bytes())
+        .map_err(|error| format!("failed to decode base64: {}", error))?;
+
+    let mut pending = writes
+        .pending
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let write = pending
+        .get_mut(&handle)
+        .ok_or_else(|| format!("unknown file write handle: {}", handle))?;
+    write
+        .file
+        .write_all(&bytes)
+        .map_err(|error| format!("failed to write {}: {}", write.temp_path.display(), error))
+}
+
+#[tauri::command]
+pub fn finish_file_write(
+    writes: tauri::State<'_, ChunkedWrites>,
+    handle: u64,
+) -> Result<String, String> {
+    let PendingWrite {
+        target,
+        temp_path,
+        file,
+    } = writes.take(handle)?;
+    let synced = file
+        .sync_all()
+        .map_err(|error| format!("failed to sync {}: {}", temp_path.display(), error));
+    // The handle must be closed before renaming, otherwise Windows refuses the move.
+    drop(file);
+    let result = synced.and_then(|_| {
+        fs::rename(&temp_path, &target)
+            .map_err(|error| format!("failed to replace {}: {}", target.display(), error))
+    });
+
+    if let Err(error) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error);
+    }
+    Ok(target.to_string_lossy().replace('\\', "/"))
+}
+
+#[tauri::command]
+pub fn abort_file_write(
+    writes: tauri::State<'_, ChunkedWrites>,
+    handle: u64,
+) -> Result<(), String> {
+    let write = writes.take(handle)?;
+    drop(write.file);
+    fs::remove_file(&write.temp_path)
+        .map_err(|error| format!("failed to remove {}: {}", write.temp_path.display(), error))
+}
@@ -0,0 +1,103 @@
+use arboard::{Clipboard, ImageData};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use std::borrow::Cow;
+use std::io::Cursor;
+use std::sync::Mutex;
+
+// Linux only serves clipboard contents while the owning `Clipboard` is alive, so keep one around.
+#[derive(Default)]
+pub struct ClipboardState(Mutex<Option<Clipboard>>);
+
+fn with_clipboard<T>(
+    state: &ClipboardState,
+    action: impl FnOnce(&mut Clipboard) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut guard = state
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if guard.is_none() {
+        let clipboard =
+            Clipboard::new().map_err(|error| format!("failed to open clipboard: {}", error))?;
+        *guard = Some(clipboard);
+    }
+
+    match guard.as_mut() {
+        Some(clipboard) => action(clipboard),
+        None => Err("clipboard is unavailable".to_string()),
+    }
+}
+
+fn encode_png(image: ImageData<'_>) -> Result<String, String> {
+    let width =
+        u32::try_from(image.width).map_err(|_| "clipboard image is too wide".to_string())?;
+    let height =
+        u32::try_from(image.height).map_err(|_| "clipboard image is too tall".to_string())?;
+    let buffer = image::RgbaImage::from_raw(width, height, image.bytes.into_owned())
+        .ok_or_else(|| "clipboard image has an unexpected pixel layout".to_string())?;
+
+    let mut encoded = Cursor::new(Vec::new());
+    buffer
+        .write_to(&mut encoded, image::ImageFormat::Png)
+        .map_err(|error| format!("failed to encode clipboard image: {}", error))?;
+    Ok(STANDARD.encode(encoded.into_inner()))
+}
+
+#[tauri::command]
+pub fn read_clipboard_image(
+    state: tauri::State<'_, ClipboardState>,
+) -> Result<Option<String>, String> {
+    with_clipboard(&state, |clipboard| match clipboard.get_image() {
+        Ok(image) => encode_png(image).map(Some),
+        Err(arboard::Error::ContentNotAvailable | arboard::Error::ConversionFailure) => Ok(None),
+        Err(error) => Err(format!("failed to read clipboard image: {}", error)),
+    })
+}
+
+#[tauri::command]
+pub fn write_clipboard_image(
+    state: tauri::State<'_, ClipboardState>,
+    data_base64: String,
+) -> Result<(), String> {
+    let bytes = STANDARD
+        .decode(data_base64.as_bytes())
+        .map_err(|error| format!("failed to decode base64: {}", error))?;
+    let decoded = image::load_from_memory(&bytes)
+        .map_err(|error| format!("failed to decode image: {}", error))?
+        .to_rgba8();
+
+    let image = ImageData {
+        width: decoded.width() as usize,
+        height: decoded.height() as usize,
+        bytes: Cow::Owned(decoded.into_raw()),
+    };
+
+    with_clipboard(&state, |clipboard| {
+        clipboard
+            .set_image(image)
+            .map_err(|error| format!("failed to write clipboard image: {}", error))
+    })
+}
+
+#[tauri::command]
+pub fn get_clipboard_text(
+    state: tauri::State<'_, ClipboardState>,
+) -> Result<Option<String>, String> {
+    with_clipboard(&state, |clipboard| match clipboard.get_text() {
+        Ok(text) => Ok(Some(text)),
+        Err(arboard::Error::ContentNotAvailable | arboard::Error::ConversionFailure) => Ok(None),
+        Err(error) => Err(format!("failed to read clipboard text: {}", error)),
+    })
+}
+
+#[tauri::command]
+pub fn set_clipboard_text(
+    state: tauri::State<'_, ClipboardState>,
+    text: String,
+) -> Result<(), String> {
+    with_clipboard(&state, |clipboard| {
+        clipboard
+            .set_text(text)
+            .map_err(|error| format!("failed to write clipboard text: {}", error))
+    })
+}
@@ -0,0 +1,52 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use tauri::Manager;
+
+fn config_file_path<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    file_name: &str,
+) -> Result<PathBuf, String> {
+    let config_dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|error| format!("failed to resolve app config dir: {}", error))?;
+    Ok(config_dir.join(file_name))
+}
+
+pub fn load_json<R: tauri::Runtime, T: DeserializeOwned>(
+    app: &tauri::AppHandle<R>,
+    file_name: &str,
+) -> Option<T> {
+    let path = config_file_path(app, file_name).ok()?;
+    let raw = fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&raw) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            log::warn!(
+                "ignoring malformed config file {}: {}",
+                path.display(),
+                error
+            );
+            None
+        }
+    }
+}
+
+pub fn save_json<R: tauri::Runtime, T: Serialize>(
+    app: &tauri::AppHandle<R>,
+    file_name: &str,
+    value: &T,
+) -> Result<(), String> {
+    let path = config_file_path(app, file_name)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create config dir: {}", error))?;
+    }
+
+    let serialized = serde_json::to_string_pretty(value)
+        .map_err(|error| format!("failed to serialize config: {}", error))?;
+    fs::write(&path, serialized)
+        .map_err(|error| format!("failed to write {}: {}", path.display(), error))
+}
@@ -0,0 +1,105 @@
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::Emitter;
+
+const DIRECTORY_CHANGE_DEBOUNCE: Duration = Duration::from_millis(300);
+
+#[derive(Default)]
+pub struct DirWatchRegistry(Mutex<HashMap<PathBuf, RecommendedWatcher>>);
+
+#[derive(Clone, Serialize)]
+struct DirectoryChanged {
+    directory: String,
+    paths: Vec<String>,
+}
+
+// Exports and copies touch a file several times, so changes are batched until the directory
+// has been quiet for the debounce window.
+fn forward_changes(
+    app: tauri::AppHandle,
+    directory: String,
+    root: PathBuf,
+    events: Receiver<Vec<PathBuf>>,
+) {
+    while let Ok(first) = events.recv() {
+        let mut changed = first.into_iter().collect::<BTreeSet<PathBuf>>();
+        loop {
+            match events.recv_timeout(DIRECTORY_CHANGE_DEBOUNCE) {
+                Ok(paths) => changed.extend(paths),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        let payload = DirectoryChanged {
+            directory: directory.clone(),
+            paths: changed
+                .iter()
+                .map(|path| crate::to_display_path(path, &root))
+                .collect(),
+        };
+        if let Err(error) = app.emit("fs://changed", payload) {
+            log::warn!("failed to emit directory change event: {}", error);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn watch_directory(
+    app: tauri::AppHandle,
+    registry: tauri::State<'_, DirWatchRegistry>,
+    path: String,
+) -> Result<(), String> {
+    let resolved = crate::resolve_scoped_path(&app, path.trim())?;
+    if !resolved.is_dir() {
+        return Err(format!("not a directory: {}", resolved.display()));
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        let Ok(event) = result else {
+            return;
+        };
+        if matches!(event.kind, notify::EventKind::Access(_)) {
+            return;
+        }
+        let pngs = event
+            .paths
+            .into_iter()
+            .filter(|changed| crate::is_png_file(changed))
+            .collect::<Vec<PathBuf>>();
+        if !pngs.is_empty() {
+            let _ = sender.send(pngs);
+        }
+    })
+    .map_err(|error| format!("failed to create directory watcher: {}", error))?;
+    watcher
+        .watch(&resolved, RecursiveMode::Recursive)
+        .map_err(|error| format!("failed to watch {}: {}", resolved.display(), error))?;
+
+    let root = crate::project_root();
+    let directory = crate::to_display_path(&resolved, &root);
+    std::thread::spawn(move || forward_changes(app, directory, root, receiver));
+    registry
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(resolved, watcher);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unwatch_directory(registry: tauri::State<'_, DirWatchRegistry>, path: String) -> bool {
+    let resolved = crate::resolve_project_path(path.trim());
+    registry
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(&resolved)
+        .is_some()
+}
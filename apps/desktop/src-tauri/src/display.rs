@@ -0,0 +1,86 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct DisplayPosition {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Serialize)]
+pub struct MonitorInfo {
+    name: Option<String>,
+    width: u32,
+    height: u32,
+    scale_factor: f64,
+    position: DisplayPosition,
+    work_area: WorkArea,
+}
+
+#[derive(Serialize)]
+pub struct WorkArea {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Serialize)]
+pub struct DisplayInfo {
+    primary: Option<MonitorInfo>,
+    current: Option<MonitorInfo>,
+    monitors: Vec<MonitorInfo>,
+}
+
+fn monitor_info(monitor: &tauri::Monitor) -> MonitorInfo {
+    let size = monitor.size();
+    let position = monitor.position();
+    let area = monitor.work_area();
+    MonitorInfo {
+        name: monitor.name().cloned(),
+        width: size.width,
+        height: size.height,
+        scale_factor: monitor.scale_factor(),
+        position: DisplayPosition {
+            x: position.x,
+            y: position.y,
+        },
+        work_area: WorkArea {
+            x: area.position.x,
+            y: area.position.y,
+            width: area.size.width,
+            height: area.size.height,
+        },
+    }
+}
+
+#[tauri::command]
+pub fn get_display_info<R: tauri::Runtime>(
+    window: tauri::Window<R>,
+    all: Option<bool>,
+) -> Result<DisplayInfo, String> {
+    let primary = window
+        .primary_monitor()
+        .map_err(|error| format!("failed to read primary monitor: {}", error))?
+        .map(|monitor| monitor_info(&monitor));
+    let current = window
+        .current_monitor()
+        .map_err(|error| format!("failed to read current monitor: {}", error))?
+        .map(|monitor| monitor_info(&monitor));
+
+    let monitors = if all.unwrap_or(false) {
+        window
+            .available_monitors()
+            .map_err(|error| format!("failed to list monitors: {}", error))?
+            .iter()
+            .map(monitor_info)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(DisplayInfo {
+        primary,
+        current,
+        monitors,
+    })
+}
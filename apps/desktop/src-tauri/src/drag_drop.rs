@@ -0,0 +1,84 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{DragDropEvent, Emitter, Manager};
+
+const ACCEPTED_EXTENSIONS: [&str; 7] = ["png", "jpg", "jpeg", "webp", "gif", "heic", "json"];
+
+#[derive(Default)]
+pub struct DragHoverState(AtomicBool);
+
+#[derive(Clone, Serialize)]
+struct DroppedFilesPayload {
+    paths: Vec<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct DragHoverPayload {
+    active: bool,
+}
+
+fn is_accepted_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|value| value.to_str())
+        .map(|value| {
+            ACCEPTED_EXTENSIONS
+                .iter()
+                .any(|accepted| value.eq_ignore_ascii_case(accepted))
+        })
+        .unwrap_or(false)
+}
+
+fn accepted_paths(paths: &[PathBuf]) -> Vec<String> {
+    let root = crate::project_root();
+    paths
+        .iter()
+        .filter(|path| is_accepted_file(path))
+        .map(|path| crate::to_display_path(path, &root))
+        .collect()
+}
+
+fn set_hover<R: tauri::Runtime>(window: &tauri::Window<R>, active: bool) {
+    let previous = window
+        .state::<DragHoverState>()
+        .0
+        .swap(active, Ordering::SeqCst);
+    if previous == active {
+        return;
+    }
+
+    if let Err(error) = window.emit("files://drag-hover", DragHoverPayload { active }) {
+        log::warn!("failed to emit drag-hover event: {}", error);
+    }
+}
+
+pub fn handle_window_event<R: tauri::Runtime>(
+    window: &tauri::Window<R>,
+    event: &tauri::WindowEvent,
+) {
+    let tauri::WindowEvent::DragDrop(drag_event) = event else {
+        return;
+    };
+
+    match drag_event {
+        DragDropEvent::Enter { paths, .. } if !accepted_paths(paths).is_empty() => {
+            set_hover(window, true)
+        }
+        DragDropEvent::Drop { paths, .. } => {
+            set_hover(window, false);
+            for path in paths.iter().filter(|path| is_accepted_file(path)) {
+                crate::path_scope::allow_path(window.app_handle(), path, false);
+            }
+            let paths = accepted_paths(paths);
+            if paths.is_empty() {
+                return;
+            }
+
+            if let Err(error) = window.emit("files://dropped", DroppedFilesPayload { paths }) {
+                log::warn!("failed to emit dropped files event: {}", error);
+            }
+        }
+        DragDropEvent::Leave => set_hover(window, false),
+        _ => {}
+    }
+}
@@ -0,0 +1,743 @@
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use image::RgbaImage;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+fn fallback_font_list() -> Vec<String> {
+    [
+        "SF Pro",
+        "SF Pro Display",
+        "SF Pro Text",
+        "Apple SD Gothic Neo",
+        "Helvetica Neue",
+        "Arial",
+        "Noto Sans",
+        "Roboto",
+        "Inter",
+    ]
+    .iter()
+    .map(|item| item.to_string())
+    .collect()
+}
+
+fn normalize_font_list(fonts: Vec<String>) -> Vec<String> {
+    let mut unique = BTreeSet::new();
+    for font in fonts {
+        let trimmed = font.trim();
+        if !trimmed.is_empty() {
+            unique.insert(trimmed.to_string());
+        }
+    }
+
+    if unique.is_empty() {
+        return fallback_font_list();
+    }
+
+    unique.into_iter().collect()
+}
+
+fn collect_system_fonts() -> Result<Vec<String>, String> {
+    #[cfg(target_os = "macos")]
+    {
+        let output = Command::new("system_profiler")
+            .args(["SPFontsDataType", "-detailLevel", "mini"])
+            .output()
+            .map_err(|error| format!("failed to execute system_profiler: {}", error))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        }
+
+        let raw = String::from_utf8_lossy(&output.stdout);
+        let mut fonts = Vec::new();
+        for line in raw.lines() {
+            let trimmed = line.trim();
+            if let Some(name) = trimmed.strip_prefix("Full Name:") {
+                fonts.push(name.trim().to_string());
+                continue;
+            }
+
+            if let Some(name) = trimmed.strip_prefix("Family:") {
+                fonts.push(name.trim().to_string());
+            }
+        }
+
+        return Ok(normalize_font_list(fonts));
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let output = Command::new("fc-list")
+            .args([":", "family"])
+            .output()
+            .map_err(|error| format!("failed to execute fc-list: {}", error))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        }
+
+        let raw = String::from_utf8_lossy(&output.stdout);
+        let mut fonts = Vec::new();
+        for line in raw.lines() {
+            let families = line.split(':').next_back().unwrap_or(line);
+            for family in families.split(',') {
+                fonts.push(family.trim().to_string());
+            }
+        }
+
+        return Ok(normalize_font_list(fonts));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let output = Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-Command",
+                "Get-ItemProperty 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Fonts' | Select-Object -Property * -ExcludeProperty PS* | ForEach-Object { $_.PSObject.Properties.Name }",
+            ])
+            .output()
+            .map_err(|error| format!("failed to execute powershell: {}", error))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        }
+
+        let raw = String::from_utf8_lossy(&output.stdout);
+        let mut fonts = Vec::new();
+        for line in raw.lines() {
+            let cleaned = line
+                .replace('\u{feff}', "")
+                .split('(')
+                .next()
+                .unwrap_or("")
+                .trim()
+                .to_string();
+
+            if !cleaned.is_empty() {
+                fonts.push(cleaned);
+            }
+        }
+
+        return Ok(normalize_font_list(fonts));
+    }
+
+    #[allow(unreachable_code)]
+    Ok(fallback_font_list())
+}
+
+const DEFAULT_PREVIEW_TEXT: &str = "The quick brown fox";
+const MAX_PREVIEW_SIZE: u32 = 512;
+const FONT_CHANGE_DEBOUNCE: Duration = Duration::from_millis(750);
+
+#[derive(Clone, Serialize)]
+pub struct FontFace {
+    family: String,
+    path: String,
+    index: u32,
+    weight: u16,
+    italic: bool,
+}
+
+fn load_font_database() -> fontdb::Database {
+    let mut database = fontdb::Database::new();
+    database.load_system_fonts();
+    database
+}
+
+fn collect_font_faces() -> Vec<FontFace> {
+    let database = load_font_database();
+    let mut faces = Vec::new();
+    for face in database.faces() {
+        let path = match &face.source {
+            fontdb::Source::File(path) => path.clone(),
+            fontdb::Source::SharedFile(path, _) => path.clone(),
+            fontdb::Source::Binary(_) => continue,
+        };
+
+        for (family, _) in &face.families {
+            faces.push(FontFace {
+                family: family.clone(),
+                path: path.to_string_lossy().replace('\\', "/"),
+                index: face.index,
+                weight: face.weight.0,
+                italic: face.style != fontdb::Style::Normal,
+            });
+        }
+    }
+
+    faces.sort_by(|left, right| {
+        left.family
+            .cmp(&right.family)
+            .then(left.weight.cmp(&right.weight))
+            .then(left.italic.cmp(&right.italic))
+    });
+    faces
+}
+
+fn find_font_face(faces: &[FontFace], family: &str) -> Option<FontFace> {
+    let wanted = family.trim();
+    faces
+        .iter()
+        .filter(|face| face.family.eq_ignore_ascii_case(wanted))
+        .min_by_key(|face| (face.italic, face.weight.abs_diff(400)))
+        .cloned()
+}
+
+fn layout_line(font: &FontRef<'_>, text: &str, size: u32) -> (Vec<ab_glyph::Glyph>, f32) {
+    let scaled = font.as_scaled(PxScale::from(size as f32));
+    let mut glyphs = Vec::new();
+    let mut caret = 0.0f32;
+    let mut previous = None;
+    for character in text.chars() {
+        let glyph_id = scaled.glyph_id(character);
+        if let Some(previous) = previous {
+            caret += scaled.kern(previous, glyph_id);
+        }
+
+        glyphs
+            .push(glyph_id.with_scale_and_position(scaled.scale(), point(caret, scaled.ascent())));
+        caret += scaled.h_advance(glyph_id);
+        previous = Some(glyph_id);
+    }
+    (glyphs, caret)
+}
+
+pub(crate) fn measure_text(font: &FontRef<'_>, text: &str, size: u32) -> f32 {
+    layout_line(font, text, size).1
+}
+
+pub(crate) fn rasterize_text(
+    font: &FontRef<'_>,
+    text: &str,
+    size: u32,
+    color: [u8; 3],
+) -> Result<RgbaImage, String> {
+    let scaled = font.as_scaled(PxScale::from(size as f32));
+    let (glyphs, caret) = layout_line(font, text, size);
+
+    let width = caret.ceil().max(1.0) as u32;
+    let height = scaled.height().ceil().max(1.0) as u32;
+    let mut image = RgbaImage::new(width, height);
+    for glyph in glyphs {
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue;
+        };
+
+        let bounds = outlined.px_bounds();
+        outlined.draw(|x, y, coverage| {
+            let px = bounds.min.x as i64 + i64::from(x);
+            let py = bounds.min.y as i64 + i64::from(y);
+            if px < 0 || py < 0 || px >= i64::from(width) || py >= i64::from(height) {
+                return;
+            }
+
+            let pixel = image.get_pixel_mut(px as u32, py as u32);
+            let alpha = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+            pixel.0 = [color[0], color[1], color[2], pixel.0[3].max(alpha)];
+        });
+    }
+
+    Ok(image)
+}
+
+type FaceKey = (String, u32);
+
+#[derive(Default)]
+pub struct FontCache {
+    families: Mutex<Option<Vec<String>>>,
+    faces: Mutex<Option<Arc<Vec<FontFace>>>>,
+    coverage: Mutex<HashMap<FaceKey, Arc<HashSet<char>>>>,
+}
+
+impl FontCache {
+    fn families(&self) -> Vec<String> {
+        let mut guard = self
+            .families
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        guard
+            .get_or_insert_with(|| collect_system_fonts().unwrap_or_else(|_| fallback_font_list()))
+            .clone()
+    }
+
+    fn faces(&self) -> Arc<Vec<FontFace>> {
+        let mut guard = self
+            .faces
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        guard
+            .get_or_insert_with(|| Arc::new(collect_font_faces()))
+            .clone()
+    }
+
+    fn invalidate(&self) {
+        *self
+            .families
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+        *self
+            .faces
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+        self.coverage
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clear();
+    }
+
+    fn coverage(&self, face: &FontFace) -> Arc<HashSet<char>> {
+        let key = (face.path.clone(), face.index);
+        if let Some(cached) = self
+            .coverage
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&key)
+        {
+            return cached.clone();
+        }
+
+        // Unreadable fonts are cached as empty so they are not re-parsed on every keystroke.
+        let characters = Arc::new(
+            load_face_data(face)
+                .ok()
+                .and_then(|data| {
+                    let font = FontRef::try_from_slice_and_index(&data, face.index).ok()?;
+                    Some(
+                        font.codepoint_ids()
+                            .map(|(_, character)| character)
+                            .collect::<HashSet<char>>(),
+                    )
+                })
+                .unwrap_or_default(),
+        );
+        self.coverage
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(key, characters.clone());
+        characters
+    }
+}
+
+#[derive(Default)]
+pub struct FontWatcher {
+    watcher: Mutex<Option<RecommendedWatcher>>,
+    autorefresh: AtomicBool,
+}
+
+#[derive(Clone, Serialize)]
+struct FontsUpdated {
+    count: usize,
+}
+
+fn font_directories<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Vec<PathBuf> {
+    let home = app.path().home_dir().ok();
+    let mut directories = Vec::new();
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(home) = &home {
+            directories.push(home.join("Library/Fonts"));
+        }
+        directories.push(PathBuf::from("/Library/Fonts"));
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(home) = &home {
+            directories.push(home.join(".fonts"));
+            directories.push(home.join(".local/share/fonts"));
+        }
+        directories.push(PathBuf::from("/usr/share/fonts"));
+        directories.push(PathBuf::from("/usr/local/share/fonts"));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let windows_dir = std::env::var("WINDIR").unwrap_or_else(|_| "C:\\Windows".to_string());
+        directories.push(PathBuf::from(windows_dir).join("Fonts"));
+        if let Some(home) = &home {
+            directories.push(home.join("AppData\\Local\\Microsoft\\Windows\\Fonts"));
+        }
+    }
+
+    directories.retain(|directory| directory.is_dir());
+    directories
+}
+
+fn forward_font_changes<R: tauri::Runtime>(app: tauri::AppHandle<R>, events: Receiver<()>) {
+    while events.recv().is_ok() {
+        loop {
+            match events.recv_timeout(FONT_CHANGE_DEBOUNCE) {
+                Ok(()) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        let cache = app.state::<FontCache>();
+        cache.invalidate();
+        if let Err(error) = app.emit("fonts://changed", ()) {
+            log::warn!("failed to emit fonts changed event: {}", error);
+        }
+
+        if app
+            .state::<FontWatcher>()
+            .autorefresh
+            .load(Ordering::SeqCst)
+        {
+            cache.faces();
+            let count = cache.families().len();
+            if let Err(error) = app.emit("fonts://updated", FontsUpdated { count }) {
+                log::warn!("failed to emit fonts updated event: {}", error);
+            }
+        }
+    }
+}
+
+pub fn start_font_watcher<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let state = app.state::<FontWatcher>();
+    let mut slot = state
+        .watcher
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if slot.is_some() {
+        return;
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        if let Ok(event) = result {
+            if !matches!(event.kind, notify::EventKind::Access(_)) {
+                let _ = sender.send(());
+            }
+        }
+    });
+    let mut watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(error) => {
+            log::warn!("failed to create font directory watcher: {}", error);
+            return;
+        }
+    };
+
+    for directory in font_directories(app) {
+        if let Err(error) = watcher.watch(&directory, RecursiveMode::Recursive) {
+            log::warn!(
+                "failed to watch font directory {}: {}",
+                directory.display(),
+                error
+            );
+        }
+    }
+
+    let handle = app.clone();
+    std::thread::spawn(move || forward_font_changes(handle, receiver));
+    *slot = Some(watcher);
+}
+
+#[tauri::command]
+pub fn set_font_autorefresh(app: tauri::AppHandle, enabled: bool) {
+    let state = app.state::<FontWatcher>();
+    state.autorefresh.store(enabled, Ordering::SeqCst);
+    if enabled {
+        start_font_watcher(&app);
+    } else {
+        // Dropping the watcher closes its channel, which ends the forwarding thread.
+        *state
+            .watcher
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    }
+}
+
+#[tauri::command]
+pub fn list_system_fonts(cache: tauri::State<'_, FontCache>) -> Result<Vec<String>, String> {
+    Ok(cache.families())
+}
+
+#[tauri::command]
+pub fn list_system_fonts_detailed(cache: tauri::State<'_, FontCache>) -> Vec<FontFace> {
+    cache.faces().as_ref().clone()
+}
+
+const FONT_USAGE_FILE: &str = "font-usage.json";
+const MAX_TRACKED_FONTS: usize = 100;
+
+#[derive(Serialize, Deserialize)]
+struct FontUsage {
+    family: String,
+    uses: u64,
+    last_used_ms: u64,
+}
+
+#[tauri::command]
+pub fn record_font_use(app: tauri::AppHandle, family: String) -> Result<(), String> {
+    let family = family.trim();
+    if family.is_empty() {
+        return Err("font family must not be empty".to_string());
+    }
+
+    let mut usage: Vec<FontUsage> =
+        crate::config_store::load_json(&app, FONT_USAGE_FILE).unwrap_or_default();
+    match usage
+        .iter_mut()
+        .find(|entry| entry.family.eq_ignore_ascii_case(family))
+    {
+        Some(entry) => {
+            entry.uses += 1;
+            entry.last_used_ms = crate::now_ms();
+        }
+        None => usage.push(FontUsage {
+            family: family.to_string(),
+            uses: 1,
+            last_used_ms: crate::now_ms(),
+        }),
+    }
+
+    usage.sort_by_key(|entry| std::cmp::Reverse((entry.uses, entry.last_used_ms)));
+    usage.truncate(MAX_TRACKED_FONTS);
+    crate::config_store::save_json(&app, FONT_USAGE_FILE, &usage)
+}
+
+#[tauri::command]
+pub fn list_recent_fonts(
+    app: tauri::AppHandle,
+    cache: tauri::State<'_, FontCache>,
+    limit: usize,
+) -> Vec<String> {
+    let installed = cache
+        .families()
+        .into_iter()
+        .map(|family| family.to_lowercase())
+        .collect::<HashSet<String>>();
+    let mut usage: Vec<FontUsage> =
+        crate::config_store::load_json(&app, FONT_USAGE_FILE).unwrap_or_default();
+    usage.retain(|entry| installed.contains(&entry.family.to_lowercase()));
+
+    // Pick the most-used families, then show them in the order they were last picked.
+    usage.sort_by_key(|entry| std::cmp::Reverse((entry.uses, entry.last_used_ms)));
+    usage.truncate(limit);
+    usage.sort_by_key(|entry| std::cmp::Reverse(entry.last_used_ms));
+    usage.into_iter().map(|entry| entry.family).collect()
+}
+
+#[tauri::command]
+pub fn render_font_preview(
+    cache: tauri::State<'_, FontCache>,
+    family: String,
+    text: String,
+    size: u32,
+) -> Result<String, String> {
+    if size == 0 || size > MAX_PREVIEW_SIZE {
+        return Err(format!(
+            "font preview size must be between 1 and {}",
+            MAX_PREVIEW_SIZE
+        ));
+    }
+
+    let face = find_font_face(&cache.faces(), &family)
+        .ok_or_else(|| format!("font family not found: {}", family))?;
+    let data = load_face_data(&face)?;
+    let font = FontRef::try_from_slice_and_index(&data, face.index)
+        .map_err(|error| format!("failed to parse {}: {}", face.path, error))?;
+
+    let sample = if text.trim().is_empty() {
+        DEFAULT_PREVIEW_TEXT
+    } else {
+        text.as_str()
+    };
+    let image = rasterize_text(&font, sample, size, [0, 0, 0])?;
+
+    let mut encoded = Cursor::new(Vec::new());
+    image
+        .write_to(&mut encoded, image::ImageFormat::Png)
+        .map_err(|error| format!("failed to encode font preview: {}", error))?;
+    Ok(STANDARD.encode(encoded.into_inner()))
+}
+
+fn load_face_data(face: &FontFace) -> Result<Vec<u8>, String> {
+    fs::read(&face.path).map_err(|error| format!("failed to read {}: {}", face.path, error))
+}
+
+// Returns the font file bytes and face index for the regular face of `family`.
+pub(crate) fn load_family_data(cache: &FontCache, family: &str) -> Result<(Vec<u8>, u32), String> {
+    let face = find_font_face(&cache.faces(), family)
+        .ok_or_else(|| format!("font family not found: {}", family))?;
+    Ok((load_face_data(&face)?, face.index))
+}
+
+fn uncovered_characters(font: &FontRef<'_>, characters: &BTreeSet<char>) -> BTreeSet<char> {
+    characters
+        .iter()
+        .copied()
+        .filter(|character| font.glyph_id(*character).0 == 0)
+        .collect()
+}
+
+#[tauri::command]
+pub fn resolve_font_fallback(
+    cache: tauri::State<'_, FontCache>,
+    family: String,
+    sample_text: String,
+) -> Result<Vec<String>, String> {
+    let faces = cache.faces();
+    let face = find_font_face(&faces, &family)
+        .ok_or_else(|| format!("font family not found: {}", family))?;
+    let data = load_face_data(&face)?;
+    let font = FontRef::try_from_slice_and_index(&data, face.index)
+        .map_err(|error| format!("failed to parse {}: {}", face.path, error))?;
+
+    let characters = sample_text
+        .chars()
+        .filter(|character| !character.is_whitespace() && !character.is_control())
+        .collect::<BTreeSet<char>>();
+    let mut missing = uncovered_characters(&font, &characters);
+    if missing.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut file_cache: HashMap<String, Option<Vec<u8>>> = HashMap::new();
+    let mut candidates = Vec::new();
+    for candidate in cache.families() {
+        if candidate.eq_ignore_ascii_case(face.family.as_str()) {
+            continue;
+        }
+        let Some(candidate_face) = find_font_face(&faces, &candidate) else {
+            continue;
+        };
+        let Some(candidate_data) = file_cache
+            .entry(candidate_face.path.clone())
+            .or_insert_with(|| load_face_data(&candidate_face).ok())
+        else {
+            continue;
+        };
+        let Ok(candidate_font) =
+            FontRef::try_from_slice_and_index(candidate_data, candidate_face.index)
+        else {
+            continue;
+        };
+
+        let uncovered = uncovered_characters(&candidate_font, &missing);
+        let covered = missing
+            .difference(&uncovered)
+            .copied()
+            .collect::<BTreeSet<char>>();
+        if !covered.is_empty() {
+            candidates.push((candidate, covered));
+        }
+    }
+
+    let mut fallbacks = Vec::new();
+    while !missing.is_empty() {
+        let best = candidates
+            .iter()
+            .enumerate()
+            .map(|(index, (_, covered))| (index, covered.intersection(&missing).count()))
+            .filter(|(_, count)| *count > 0)
+            .max_by_key(|(index, count)| (*count, std::cmp::Reverse(*index)));
+        let Some((index, _)) = best else {
+            break;
+        };
+
+        let (candidate, covered) = candidates.swap_remove(index);
+        missing.retain(|character| !covered.contains(character));
+        fallbacks.push(candidate);
+    }
+
+    Ok(fallbacks)
+}
+
+#[tauri::command]
+pub fn fonts_covering_text(cache: tauri::State<'_, FontCache>, sample_text: String) -> Vec<String> {
+    let characters = sample_text
+        .chars()
+        .filter(|character| !character.is_whitespace() && !character.is_control())
+        .collect::<BTreeSet<char>>();
+
+    let mut families = BTreeSet::new();
+    for face in cache.faces().iter() {
+        if families.contains(&face.family) {
+            continue;
+        }
+        let coverage = cache.coverage(face);
+        if characters
+            .iter()
+            .all(|character| coverage.contains(character))
+        {
+            families.insert(face.family.clone());
+        }
+    }
+    families.into_iter().collect()
+}
+
+#[derive(Serialize)]
+pub struct FontFileInfo {
+    index: u32,
+    family: Option<String>,
+    subfamily: Option<String>,
+    full_name: Option<String>,
+    postscript_name: Option<String>,
+    is_variable: bool,
+    weight: u16,
+    license_url: Option<String>,
+}
+
+fn font_name(face: &ttf_parser::Face<'_>, ids: &[u16]) -> Option<String> {
+    ids.iter().find_map(|id| {
+        face.names()
+            .into_iter()
+            .filter(|name| name.name_id == *id)
+            .find_map(|name| name.to_string())
+            .filter(|value| !value.trim().is_empty())
+    })
+}
+
+fn describe_face(face: &ttf_parser::Face<'_>, index: u32) -> FontFileInfo {
+    use ttf_parser::name_id;
+
+    FontFileInfo {
+        index,
+        family: font_name(face, &[name_id::TYPOGRAPHIC_FAMILY, name_id::FAMILY]),
+        subfamily: font_name(face, &[name_id::TYPOGRAPHIC_SUBFAMILY, name_id::SUBFAMILY]),
+        full_name: font_name(face, &[name_id::FULL_NAME]),
+        postscript_name: font_name(face, &[name_id::POST_SCRIPT_NAME]),
+        is_variable: face.is_variable(),
+        weight: face.weight().to_number(),
+        license_url: font_name(face, &[name_id::LICENSE_URL]),
+    }
+}
+
+#[tauri::command]
+pub fn read_font_info(app: tauri::AppHandle, path: String) -> Result<Vec<FontFileInfo>, String> {
+    let resolved = crate::resolve_scoped_path(&app, path.trim())?;
+    let data = fs::read(&resolved)
+        .map_err(|error| format!("failed to read {}: {}", resolved.display(), error))?;
+
+    let face_count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+    (0..face_count)
+        .map(|index| {
+            ttf_parser::Face::parse(&data, index)
+                .map(|face| describe_face(&face, index))
+                .map_err(|error| {
+                    format!(
+                        "failed to parse face {} of {}: {}",
+                        index,
+                        resolved.display(),
+                        error
+                    )
+                })
+        })
+        .collect()
+}
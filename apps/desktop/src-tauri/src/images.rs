@@ -0,0 +1,1078 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use image::{ImageDecoder, ImageFormat, ImageReader};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use tauri::Emitter;
+
+const HEADER_FORMATS: [ImageFormat; 3] = [ImageFormat::Png, ImageFormat::Jpeg, ImageFormat::WebP];
+
+#[derive(Serialize)]
+pub struct ImageDimensions {
+    width: u32,
+    height: u32,
+}
+
+pub(crate) fn read_header_dimensions(path: &Path) -> Result<(u32, u32), String> {
+    let reader = ImageReader::open(path)
+        .map_err(|error| format!("failed to open {}: {}", path.display(), error))?
+        .with_guessed_format()
+        .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+
+    match reader.format() {
+        Some(format) if HEADER_FORMATS.contains(&format) => {}
+        _ => return Err(format!("unsupported image format: {}", path.display())),
+    }
+
+    reader
+        .into_dimensions()
+        .map_err(|error| format!("corrupt image header in {}: {}", path.display(), error))
+}
+
+#[tauri::command]
+pub fn get_image_dimensions(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<ImageDimensions, String> {
+    let resolved = crate::resolve_scoped_path(&app, &path)?;
+    let (width, height) = read_header_dimensions(&resolved)?;
+    Ok(ImageDimensions { width, height })
+}
+
+#[derive(Serialize)]
+pub struct OptimizeResult {
+    before_bytes: u64,
+    after_bytes: u64,
+}
+
+#[derive(Serialize)]
+pub struct BatchOptimizeResult {
+    files: usize,
+    before_bytes: u64,
+    after_bytes: u64,
+    errors: Vec<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct BatchProgress {
+    done: usize,
+    total: usize,
+    path: String,
+}
+
+fn optimize_png_file(source: &Path, destination: &Path) -> Result<OptimizeResult, String> {
+    let original = fs::read(source)
+        .map_err(|error| format!("failed to read {}: {}", source.display(), error))?;
+    let optimized = oxipng::optimize_from_memory(&original, &oxipng::Options::default())
+        .map_err(|error| format!("failed to optimize {}: {}", source.display(), error))?;
+
+    let output = if optimized.len() < original.len() {
+        optimized
+    } else {
+        original.clone()
+    };
+
+    if destination != source || output.len() < original.len() {
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+        }
+        crate::write_atomically(destination, &output)?;
+    }
+
+    Ok(OptimizeResult {
+        before_bytes: original.len() as u64,
+        after_bytes: output.len() as u64,
+    })
+}
+
+#[tauri::command]
+pub fn optimize_png(
+    app: tauri::AppHandle,
+    path: String,
+    in_place: bool,
+    dst: Option<String>,
+) -> Result<OptimizeResult, String> {
+    let source = crate::resolve_scoped_path(&app, &path)?;
+    let destination = match (in_place, dst) {
+        (true, _) => source.clone(),
+        (false, Some(dst)) if !dst.trim().is_empty() => {
+            crate::resolve_scoped_path(&app, dst.trim())?
+        }
+        (false, _) => return Err("dst is required when in_place is false".to_string()),
+    };
+
+    optimize_png_file(&source, &destination)
+}
+
+#[tauri::command]
+pub async fn optimize_pngs(
+    app: tauri::AppHandle,
+    dir: String,
+) -> Result<BatchOptimizeResult, String> {
+    let resolved = crate::resolve_scoped_path(&app, &dir)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut files = crate::collect_png_files(&resolved)?;
+        files.sort();
+
+        let root = crate::project_root();
+        let total = files.len();
+        let mut summary = BatchOptimizeResult {
+            files: total,
+            before_bytes: 0,
+            after_bytes: 0,
+            errors: Vec::new(),
+        };
+
+        for (index, file) in files.iter().enumerate() {
+            match optimize_png_file(file, file) {
+                Ok(result) => {
+                    summary.before_bytes += result.before_bytes;
+                    summary.after_bytes += result.after_bytes;
+                }
+                Err(error) => summary.errors.push(error),
+            }
+
+            let progress = BatchProgress {
+                done: index + 1,
+                total,
+                path: crate::to_display_path(file, &root),
+            };
+            if let Err(error) = app.emit("optimize://progress", progress) {
+                log::warn!("failed to emit optimize progress event: {}", error);
+            }
+        }
+
+        Ok(summary)
+    })
+    .await
+    .map_err(|error| format!("failed to join optimize_pngs task: {}", error))?
+}
+
+pub(crate) struct ProfiledImage {
+    pub(crate) image: image::DynamicImage,
+    pub(crate) format: ImageFormat,
+    pub(crate) icc_profile: Option<Vec<u8>>,
+}
+
+fn convert_to_srgb(
+    image: image::DynamicImage,
+    icc_profile: &[u8],
+) -> Result<image::DynamicImage, String> {
+    let source = moxcms::ColorProfile::new_from_slice(icc_profile)
+        .map_err(|error| format!("failed to parse ICC profile: {:?}", error))?;
+    let transform = source
+        .create_transform_8bit(
+            moxcms::Layout::Rgba,
+            &moxcms::ColorProfile::new_srgb(),
+            moxcms::Layout::Rgba,
+            moxcms::TransformOptions::default(),
+        )
+        .map_err(|error| format!("failed to build sRGB transform: {:?}", error))?;
+
+    let has_alpha = image.color().has_alpha();
+    let rgba = image.to_rgba8();
+    let mut converted = image::RgbaImage::new(rgba.width(), rgba.height());
+    transform
+        .transform(rgba.as_raw(), &mut converted)
+        .map_err(|error| format!("failed to convert to sRGB: {:?}", error))?;
+
+    let converted = image::DynamicImage::ImageRgba8(converted);
+    Ok(if has_alpha {
+        converted
+    } else {
+        image::DynamicImage::ImageRgb8(converted.to_rgb8())
+    })
+}
+
+// With `assume_srgb` the pixels are converted to sRGB and the profile dropped; otherwise the
+// embedded profile is carried along so `encode_with_profile` can write it back out.
+pub(crate) fn open_profiled(path: &Path, assume_srgb: bool) -> Result<ProfiledImage, String> {
+    let reader = ImageReader::open(path)
+        .map_err(|error| format!("failed to open {}: {}", path.display(), error))?
+        .with_guessed_format()
+        .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+    let format = reader
+        .format()
+        .ok_or_else(|| format!("unsupported image format: {}", path.display()))?;
+    let mut decoder = reader
+        .into_decoder()
+        .map_err(|error| format!("failed to decode {}: {}", path.display(), error))?;
+    let icc_profile = decoder.icc_profile().map_err(|error| {
+        format!(
+            "failed to read ICC profile of {}: {}",
+            path.display(),
+            error
+        )
+    })?;
+    let image = image::DynamicImage::from_decoder(decoder)
+        .map_err(|error| format!("failed to decode {}: {}", path.display(), error))?;
+
+    match icc_profile {
+        Some(profile) if assume_srgb => match convert_to_srgb(image.clone(), &profile) {
+            Ok(converted) => Ok(ProfiledImage {
+                image: converted,
+                format,
+                icc_profile: None,
+            }),
+            Err(error) => {
+                log::warn!("keeping original colors of {}: {}", path.display(), error);
+                Ok(ProfiledImage {
+                    image,
+                    format,
+                    icc_profile: Some(profile),
+                })
+            }
+        },
+        icc_profile => Ok(ProfiledImage {
+            image,
+            format,
+            icc_profile,
+        }),
+    }
+}
+
+pub(crate) fn encode_with_profile(
+    image: &image::DynamicImage,
+    format: ImageFormat,
+    icc_profile: Option<&[u8]>,
+) -> Result<Vec<u8>, String> {
+    use image::ImageEncoder;
+
+    let mut encoded = Cursor::new(Vec::new());
+    let Some(profile) = icc_profile else {
+        image
+            .write_to(&mut encoded, format)
+            .map_err(|error| format!("failed to encode image: {}", error))?;
+        return Ok(encoded.into_inner());
+    };
+
+    let attach = |encoder: &mut dyn ImageEncoder| {
+        if let Err(error) = encoder.set_icc_profile(profile.to_vec()) {
+            log::warn!("dropping ICC profile: {}", error);
+        }
+    };
+    let written = match format {
+        ImageFormat::Png => {
+            let mut encoder = image::codecs::png::PngEncoder::new(&mut encoded);
+            attach(&mut encoder);
+            image.write_with_encoder(encoder)
+        }
+        ImageFormat::Jpeg => {
+            let mut encoder = image::codecs::jpeg::JpegEncoder::new(&mut encoded);
+            attach(&mut encoder);
+            image.write_with_encoder(encoder)
+        }
+        ImageFormat::WebP => {
+            let mut encoder = image::codecs::webp::WebPEncoder::new_lossless(&mut encoded);
+            attach(&mut encoder);
+            image.write_with_encoder(encoder)
+        }
+        other => image.write_to(&mut encoded, other),
+    };
+    written.map_err(|error| format!("failed to encode image: {}", error))?;
+    Ok(encoded.into_inner())
+}
+
+#[derive(Serialize)]
+pub struct ColorProfileInfo {
+    has_profile: bool,
+    description: Option<String>,
+    bytes: usize,
+}
+
+fn profile_description(text: &moxcms::ProfileText) -> Option<String> {
+    let description = match text {
+        moxcms::ProfileText::PlainString(value) => value.clone(),
+        moxcms::ProfileText::Localizable(values) => values
+            .iter()
+            .find(|value| value.language.eq_ignore_ascii_case("en"))
+            .or_else(|| values.first())
+            .map(|value| value.value.clone())?,
+        moxcms::ProfileText::Description(value) if !value.ascii_string.trim().is_empty() => {
+            value.ascii_string.clone()
+        }
+        moxcms::ProfileText::Description(value) => value.unicode_string.clone(),
+    };
+    let trimmed = description.trim_matches(char::from(0)).trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+#[tauri::command]
+pub fn read_color_profile(app: tauri::AppHandle, path: String) -> Result<ColorProfileInfo, String> {
+    let resolved = crate::resolve_scoped_path(&app, &path)?;
+    let mut decoder = ImageReader::open(&resolved)
+        .map_err(|error| format!("failed to open {}: {}", resolved.display(), error))?
+        .with_guessed_format()
+        .map_err(|error| format!("failed to read {}: {}", resolved.display(), error))?
+        .into_decoder()
+        .map_err(|error| format!("failed to decode {}: {}", resolved.display(), error))?;
+    let Some(profile) = decoder.icc_profile().map_err(|error| {
+        format!(
+            "failed to read ICC profile of {}: {}",
+            resolved.display(),
+            error
+        )
+    })?
+    else {
+        return Ok(ColorProfileInfo {
+            has_profile: false,
+            description: None,
+            bytes: 0,
+        });
+    };
+
+    let description = moxcms::ColorProfile::new_from_slice(&profile)
+        .ok()
+        .and_then(|parsed| parsed.description.as_ref().and_then(profile_description));
+    Ok(ColorProfileInfo {
+        has_profile: true,
+        description,
+        bytes: profile.len(),
+    })
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn crop_image(
+    app: tauri::AppHandle,
+    src: String,
+    dst: String,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    assume_srgb: Option<bool>,
+) -> Result<(), String> {
+    let source = crate::resolve_scoped_path(&app, &src)?;
+    let destination = crate::resolve_scoped_path(&app, &dst)?;
+
+    let ProfiledImage {
+        image: decoded,
+        format,
+        icc_profile,
+    } = open_profiled(&source, assume_srgb.unwrap_or(false))?;
+
+    if width == 0 || height == 0 {
+        return Err(format!(
+            "crop size must be non-zero, got {}x{}",
+            width, height
+        ));
+    }
+    let fits_horizontally = x
+        .checked_add(width)
+        .is_some_and(|right| right <= decoded.width());
+    let fits_vertically = y
+        .checked_add(height)
+        .is_some_and(|bottom| bottom <= decoded.height());
+    if !fits_horizontally || !fits_vertically {
+        return Err(format!(
+            "crop rectangle {}x{} at ({}, {}) is outside the {}x{} source image",
+            width,
+            height,
+            x,
+            y,
+            decoded.width(),
+            decoded.height()
+        ));
+    }
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+    }
+
+    let cropped = decoded.crop_imm(x, y, width, height);
+    let encoded = encode_with_profile(&cropped, format, icc_profile.as_deref())?;
+    fs::write(&destination, encoded)
+        .map_err(|error| format!("failed to write {}: {}", destination.display(), error))
+}
+
+fn svg_target_size(
+    intrinsic: resvg::tiny_skia::Size,
+    width: Option<u32>,
+    height: Option<u32>,
+) -> Result<(u32, u32), String> {
+    let aspect = intrinsic.width() / intrinsic.height();
+    let (width, height) = match (width, height) {
+        (Some(width), Some(height)) => (width, height),
+        (Some(width), None) => (width, (width as f32 / aspect).round() as u32),
+        (None, Some(height)) => ((height as f32 * aspect).round() as u32, height),
+        (None, None) => (
+            intrinsic.width().ceil() as u32,
+            intrinsic.height().ceil() as u32,
+        ),
+    };
+
+    if width == 0 || height == 0 {
+        return Err(format!("invalid raster size: {}x{}", width, height));
+    }
+    Ok((width, height))
+}
+
+#[tauri::command]
+pub fn rasterize_svg(
+    app: tauri::AppHandle,
+    src: String,
+    dst: String,
+    width: Option<u32>,
+    height: Option<u32>,
+) -> Result<(), String> {
+    let source = crate::resolve_scoped_path(&app, &src)?;
+    let destination = crate::resolve_scoped_path(&app, &dst)?;
+
+    let data = fs::read(&source)
+        .map_err(|error| format!("failed to read {}: {}", source.display(), error))?;
+    let mut options = resvg::usvg::Options {
+        resources_dir: source.parent().map(Path::to_path_buf),
+        ..Default::default()
+    };
+    options.fontdb_mut().load_system_fonts();
+    let tree = resvg::usvg::Tree::from_data(&data, &options)
+        .map_err(|error| format!("malformed SVG {}: {}", source.display(), error))?;
+
+    let (target_width, target_height) = svg_target_size(tree.size(), width, height)?;
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(target_width, target_height)
+        .ok_or_else(|| format!("invalid raster size: {}x{}", target_width, target_height))?;
+    let transform = resvg::tiny_skia::Transform::from_scale(
+        target_width as f32 / tree.size().width(),
+        target_height as f32 / tree.size().height(),
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+    }
+    pixmap
+        .save_png(&destination)
+        .map_err(|error| format!("failed to write {}: {}", destination.display(), error))
+}
+
+#[tauri::command]
+pub async fn generate_contact_sheet(
+    app: tauri::AppHandle,
+    dir: String,
+    dst: String,
+    columns: u32,
+    cell: u32,
+    gap: u32,
+) -> Result<ImageDimensions, String> {
+    if columns == 0 || cell == 0 {
+        return Err("columns and cell must be greater than zero".to_string());
+    }
+
+    let source = crate::resolve_scoped_path(&app, &dir)?;
+    let destination = crate::resolve_scoped_path(&app, &dst)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut files = crate::collect_png_files(&source)?;
+        files.retain(|file| file != &destination);
+        files.sort();
+        if files.is_empty() {
+            return Err(format!("no PNG images found in {}", source.display()));
+        }
+
+        let total = files.len();
+        let columns = columns.min(total as u32);
+        let rows = (total as u32).div_ceil(columns);
+        let span = |count: u32| -> Result<u32, String> {
+            count
+                .checked_mul(cell)
+                .and_then(|cells| cells.checked_add((count - 1).checked_mul(gap)?))
+                .ok_or_else(|| "contact sheet is too large".to_string())
+        };
+        let width = span(columns)?;
+        let height = span(rows)?;
+        let mut canvas = image::RgbaImage::new(width, height);
+
+        let root = crate::project_root();
+        for (index, file) in files.iter().enumerate() {
+            let thumbnail = image::open(file)
+                .map_err(|error| format!("failed to decode {}: {}", file.display(), error))?
+                .thumbnail(cell, cell)
+                .to_rgba8();
+
+            let column = index as u32 % columns;
+            let row = index as u32 / columns;
+            let x = column * (cell + gap) + (cell - thumbnail.width()) / 2;
+            let y = row * (cell + gap) + (cell - thumbnail.height()) / 2;
+            image::imageops::overlay(&mut canvas, &thumbnail, i64::from(x), i64::from(y));
+
+            let progress = BatchProgress {
+                done: index + 1,
+                total,
+                path: crate::to_display_path(file, &root),
+            };
+            if let Err(error) = app.emit("contact-sheet://progress", progress) {
+                log::warn!("failed to emit contact sheet progress event: {}", error);
+            }
+        }
+
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+        }
+        canvas
+            .save_with_format(&destination, ImageFormat::Png)
+            .map_err(|error| format!("failed to write {}: {}", destination.display(), error))?;
+        Ok(ImageDimensions { width, height })
+    })
+    .await
+    .map_err(|error| format!("failed to join generate_contact_sheet task: {}", error))?
+}
+
+pub(crate) fn parse_hex_color(value: &str) -> Result<[u8; 3], String> {
+    let invalid = || format!("invalid hex color: {}", value);
+    let digits = value.trim().trim_start_matches('#');
+    if !digits
+        .chars()
+        .all(|character| character.is_ascii_hexdigit())
+    {
+        return Err(invalid());
+    }
+
+    let expanded = match digits.len() {
+        3 => digits.chars().flat_map(|digit| [digit, digit]).collect(),
+        6 => digits.to_string(),
+        _ => return Err(invalid()),
+    };
+    let channel = |index: usize| u8::from_str_radix(&expanded[index..index + 2], 16);
+    match (channel(0), channel(2), channel(4)) {
+        (Ok(red), Ok(green), Ok(blue)) => Ok([red, green, blue]),
+        _ => Err(invalid()),
+    }
+}
+
+fn blend_channel(foreground: u8, background: u8, alpha: u8) -> u8 {
+    let alpha = u16::from(alpha);
+    ((u16::from(foreground) * alpha + u16::from(background) * (255 - alpha) + 127) / 255) as u8
+}
+
+#[tauri::command]
+pub fn flatten_image(
+    app: tauri::AppHandle,
+    src: String,
+    dst: String,
+    background: String,
+) -> Result<(), String> {
+    let background = parse_hex_color(&background)?;
+    let source = crate::resolve_scoped_path(&app, &src)?;
+    let destination = crate::resolve_scoped_path(&app, &dst)?;
+
+    let reader = ImageReader::open(&source)
+        .map_err(|error| format!("failed to open {}: {}", source.display(), error))?
+        .with_guessed_format()
+        .map_err(|error| format!("failed to read {}: {}", source.display(), error))?;
+    let source_format = reader.format();
+    let decoded = reader
+        .decode()
+        .map_err(|error| format!("failed to decode {}: {}", source.display(), error))?;
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+    }
+
+    let same_format =
+        source_format.is_some() && ImageFormat::from_path(&destination).ok() == source_format;
+    if !decoded.color().has_alpha() && same_format {
+        if source != destination {
+            fs::copy(&source, &destination)
+                .map_err(|error| format!("failed to copy {}: {}", source.display(), error))?;
+        }
+        return Ok(());
+    }
+
+    let rgba = decoded.to_rgba8();
+    let mut flattened = image::RgbImage::new(rgba.width(), rgba.height());
+    for (pixel, output) in rgba.pixels().zip(flattened.pixels_mut()) {
+        let [red, green, blue, alpha] = pixel.0;
+        output.0 = [
+            blend_channel(red, background[0], alpha),
+            blend_channel(green, background[1], alpha),
+            blend_channel(blue, background[2], alpha),
+        ];
+    }
+
+    flattened
+        .save(&destination)
+        .map_err(|error| format!("failed to write {}: {}", destination.display(), error))
+}
+
+#[derive(Serialize)]
+pub struct ImageMetadata {
+    orientation: Option<u16>,
+    datetime: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+    make: Option<String>,
+    model: Option<String>,
+}
+
+fn read_exif(path: &Path) -> Result<Option<exif::Exif>, String> {
+    let file = fs::File::open(path)
+        .map_err(|error| format!("failed to open {}: {}", path.display(), error))?;
+    match exif::Reader::new().read_from_container(&mut std::io::BufReader::new(file)) {
+        Ok(parsed) => Ok(Some(parsed)),
+        Err(
+            exif::Error::NotFound(_) | exif::Error::BlankValue(_) | exif::Error::InvalidFormat(_),
+        ) => Ok(None),
+        Err(error) => Err(format!(
+            "failed to read EXIF from {}: {}",
+            path.display(),
+            error
+        )),
+    }
+}
+
+fn exif_text(parsed: &exif::Exif, tag: exif::Tag) -> Option<String> {
+    let field = parsed.get_field(tag, exif::In::PRIMARY)?;
+    let text = match &field.value {
+        exif::Value::Ascii(values) => values
+            .iter()
+            .map(|value| String::from_utf8_lossy(value).to_string())
+            .collect::<Vec<String>>()
+            .join(" "),
+        _ => field.display_value().to_string(),
+    };
+    let trimmed =
+        text.trim_matches(|character: char| character == '\0' || character.is_whitespace());
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn exif_orientation(parsed: &exif::Exif) -> Option<u16> {
+    parsed
+        .get_field(exif::Tag::Orientation, exif::In::PRIMARY)?
+        .value
+        .get_uint(0)
+        .and_then(|value| u16::try_from(value).ok())
+}
+
+#[tauri::command]
+pub fn read_image_metadata(app: tauri::AppHandle, path: String) -> Result<ImageMetadata, String> {
+    let resolved = crate::resolve_scoped_path(&app, &path)?;
+    let parsed = read_exif(&resolved)?;
+    let (width, height) = match image::image_dimensions(&resolved) {
+        Ok((width, height)) => (Some(width), Some(height)),
+        Err(_) => (None, None),
+    };
+
+    Ok(ImageMetadata {
+        orientation: parsed.as_ref().and_then(exif_orientation),
+        datetime: parsed
+            .as_ref()
+            .and_then(|parsed| exif_text(parsed, exif::Tag::DateTimeOriginal))
+            .or_else(|| {
+                parsed
+                    .as_ref()
+                    .and_then(|parsed| exif_text(parsed, exif::Tag::DateTime))
+            }),
+        width,
+        height,
+        make: parsed
+            .as_ref()
+            .and_then(|parsed| exif_text(parsed, exif::Tag::Make)),
+        model: parsed
+            .as_ref()
+            .and_then(|parsed| exif_text(parsed, exif::Tag::Model)),
+    })
+}
+
+#[tauri::command]
+pub fn auto_orient_image(app: tauri::AppHandle, src: String, dst: String) -> Result<(), String> {
+    let source = crate::resolve_scoped_path(&app, &src)?;
+    let destination = crate::resolve_scoped_path(&app, &dst)?;
+    let orientation = read_exif(&source)?
+        .as_ref()
+        .and_then(exif_orientation)
+        .and_then(|value| u8::try_from(value).ok())
+        .and_then(image::metadata::Orientation::from_exif)
+        .unwrap_or(image::metadata::Orientation::NoTransforms);
+
+    let reader = ImageReader::open(&source)
+        .map_err(|error| format!("failed to open {}: {}", source.display(), error))?
+        .with_guessed_format()
+        .map_err(|error| format!("failed to read {}: {}", source.display(), error))?;
+    let format = reader
+        .format()
+        .ok_or_else(|| format!("unsupported image format: {}", source.display()))?;
+    let mut decoded = reader
+        .decode()
+        .map_err(|error| format!("failed to decode {}: {}", source.display(), error))?;
+    decoded.apply_orientation(orientation);
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+    }
+    // Re-encoding drops the source metadata, which also removes the orientation tag.
+    decoded
+        .save_with_format(&destination, format)
+        .map_err(|error| format!("failed to write {}: {}", destination.display(), error))
+}
+
+#[derive(Serialize)]
+pub struct ImageDiff {
+    differing_pixels: u64,
+    total_pixels: u64,
+    max_channel_delta: u8,
+}
+
+#[tauri::command]
+pub fn diff_images(
+    app: tauri::AppHandle,
+    a: String,
+    b: String,
+    out: Option<String>,
+) -> Result<ImageDiff, String> {
+    let open_rgba = |path: &Path| {
+        image::open(path)
+            .map(|decoded| decoded.to_rgba8())
+            .map_err(|error| format!("failed to decode {}: {}", path.display(), error))
+    };
+    let first_path = crate::resolve_scoped_path(&app, &a)?;
+    let second_path = crate::resolve_scoped_path(&app, &b)?;
+    let first = open_rgba(&first_path)?;
+    let second = open_rgba(&second_path)?;
+    if first.dimensions() != second.dimensions() {
+        return Err(format!(
+            "image dimensions differ: {}x{} vs {}x{}",
+            first.width(),
+            first.height(),
+            second.width(),
+            second.height()
+        ));
+    }
+
+    let mut heatmap = out
+        .as_ref()
+        .map(|_| image::RgbaImage::new(first.width(), first.height()));
+    let mut differing_pixels = 0u64;
+    let mut max_channel_delta = 0u8;
+    for ((x, y, left), right) in first.enumerate_pixels().zip(second.pixels()) {
+        let delta = left
+            .0
+            .iter()
+            .zip(right.0.iter())
+            .map(|(left, right)| left.abs_diff(*right))
+            .max()
+            .unwrap_or(0);
+        if delta == 0 {
+            continue;
+        }
+
+        differing_pixels += 1;
+        max_channel_delta = max_channel_delta.max(delta);
+        if let Some(heatmap) = heatmap.as_mut() {
+            heatmap.put_pixel(x, y, image::Rgba([255, 0, 0, delta.max(64)]));
+        }
+    }
+
+    if let (Some(out), Some(heatmap)) = (out, heatmap) {
+        let destination = crate::resolve_scoped_path(&app, &out)?;
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+        }
+        heatmap
+            .save_with_format(&destination, ImageFormat::Png)
+            .map_err(|error| format!("failed to write {}: {}", destination.display(), error))?;
+    }
+
+    Ok(ImageDiff {
+        differing_pixels,
+        total_pixels: u64::from(first.width()) * u64::from(first.height()),
+        max_channel_delta,
+    })
+}
+
+pub(crate) fn encode_image(
+    decoded: &image::DynamicImage,
+    format: &str,
+    quality: Option<u8>,
+) -> Result<Vec<u8>, String> {
+    let mut encoded = std::io::Cursor::new(Vec::new());
+    match format.trim().to_ascii_lowercase().as_str() {
+        "png" => decoded.write_to(&mut encoded, ImageFormat::Png),
+        "jpeg" | "jpg" => {
+            let quality = quality.unwrap_or(90).clamp(1, 100);
+            let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut encoded, quality);
+            decoded.to_rgb8().write_with_encoder(encoder)
+        }
+        // The bundled WebP encoder is lossless only, so `quality` does not apply.
+        "webp" => decoded.to_rgba8().write_to(&mut encoded, ImageFormat::WebP),
+        other => return Err(format!("unsupported image format: {}", other)),
+    }
+    .map_err(|error| format!("failed to encode {}: {}", format, error))?;
+    Ok(encoded.into_inner())
+}
+
+#[tauri::command]
+pub fn save_image_base64(
+    app: tauri::AppHandle,
+    path: String,
+    data_base64: String,
+    format: String,
+    quality: Option<u8>,
+) -> Result<(), String> {
+    let bytes = STANDARD
+        .decode(data_base64.as_bytes())
+        .map_err(|error| format!("failed to decode base64: {}", error))?;
+    let decoded = image::load_from_memory_with_format(&bytes, ImageFormat::Png)
+        .map_err(|error| format!("failed to decode PNG data: {}", error))?;
+    let encoded = encode_image(&decoded, &format, quality)?;
+
+    let destination = crate::resolve_scoped_path(&app, &path)?;
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+    }
+    crate::write_atomically(&destination, &encoded)
+}
+
+#[derive(Deserialize)]
+pub struct ScreenRect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+fn rounded_corner_coverage(x: u32, y: u32, width: u32, height: u32, radius: f32) -> f32 {
+    let px = x as f32 + 0.5;
+    let py = y as f32 + 0.5;
+    let cx = px.clamp(radius, width as f32 - radius);
+    let cy = py.clamp(radius, height as f32 - radius);
+    let distance = ((px - cx).powi(2) + (py - cy).powi(2)).sqrt();
+    (radius - distance + 0.5).clamp(0.0, 1.0)
+}
+
+pub(crate) fn apply_corner_radius(image: &mut image::RgbaImage, radius: u32) {
+    let (width, height) = image.dimensions();
+    let radius = radius.min(width / 2).min(height / 2) as f32;
+    if radius <= 0.0 {
+        return;
+    }
+
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        let coverage = rounded_corner_coverage(x, y, width, height, radius);
+        if coverage < 1.0 {
+            pixel.0[3] = (f32::from(pixel.0[3]) * coverage).round() as u8;
+        }
+    }
+}
+
+#[tauri::command]
+pub fn composite_onto_frame(
+    app: tauri::AppHandle,
+    screenshot: String,
+    frame: String,
+    screen_rect: ScreenRect,
+    out: String,
+    corner_radius: Option<u32>,
+) -> Result<(), String> {
+    let screenshot_path = crate::resolve_scoped_path(&app, &screenshot)?;
+    let frame_path = crate::resolve_scoped_path(&app, &frame)?;
+    let destination = crate::resolve_scoped_path(&app, &out)?;
+
+    let frame_image = image::open(&frame_path)
+        .map_err(|error| format!("failed to decode {}: {}", frame_path.display(), error))?
+        .to_rgba8();
+    let fits_horizontally = screen_rect
+        .x
+        .checked_add(screen_rect.width)
+        .is_some_and(|right| right <= frame_image.width());
+    let fits_vertically = screen_rect
+        .y
+        .checked_add(screen_rect.height)
+        .is_some_and(|bottom| bottom <= frame_image.height());
+    if screen_rect.width == 0 || screen_rect.height == 0 || !fits_horizontally || !fits_vertically {
+        return Err(format!(
+            "screen rect {}x{} at ({}, {}) does not fit the {}x{} frame",
+            screen_rect.width,
+            screen_rect.height,
+            screen_rect.x,
+            screen_rect.y,
+            frame_image.width(),
+            frame_image.height()
+        ));
+    }
+
+    let mut screen = image::open(&screenshot_path)
+        .map_err(|error| format!("failed to decode {}: {}", screenshot_path.display(), error))?
+        .resize_to_fill(
+            screen_rect.width,
+            screen_rect.height,
+            image::imageops::FilterType::Lanczos3,
+        )
+        .to_rgba8();
+    if let Some(radius) = corner_radius {
+        apply_corner_radius(&mut screen, radius);
+    }
+
+    let mut canvas = image::RgbaImage::new(frame_image.width(), frame_image.height());
+    image::imageops::overlay(
+        &mut canvas,
+        &screen,
+        i64::from(screen_rect.x),
+        i64::from(screen_rect.y),
+    );
+    image::imageops::overlay(&mut canvas, &frame_image, 0, 0);
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+    }
+    canvas
+        .save(&destination)
+        .map_err(|error| format!("failed to write {}: {}", destination.display(), error))
+}
+
+// dHash: compare each pixel of a 9x8 grayscale thumbnail with its right neighbour. Re-encodes and
+// small resizes flip only a few of the 64 bits, so Hamming distance approximates visual distance.
+fn difference_hash(path: &Path) -> Result<u64, String> {
+    let gray = image::open(path)
+        .map_err(|error| format!("failed to decode {}: {}", path.display(), error))?
+        .resize_exact(9, 8, image::imageops::FilterType::Triangle)
+        .to_luma8();
+
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            let brighter = gray.get_pixel(x, y)[0] > gray.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(brighter);
+        }
+    }
+    Ok(hash)
+}
+
+#[tauri::command]
+pub async fn perceptual_hash(app: tauri::AppHandle, path: String) -> Result<String, String> {
+    let resolved = crate::resolve_scoped_path(&app, &path)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        difference_hash(&resolved).map(|hash| format!("{:016x}", hash))
+    })
+    .await
+    .map_err(|error| format!("failed to join perceptual_hash task: {}", error))?
+}
+
+// Each image joins the first group whose representative (its first member) is within
+// max_distance, so a chain of small differences cannot pull unrelated images together.
+fn cluster_by_hash(hashes: &[u64], max_distance: u32) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (index, hash) in hashes.iter().enumerate() {
+        match groups
+            .iter_mut()
+            .find(|group| (hashes[group[0]] ^ hash).count_ones() <= max_distance)
+        {
+            Some(group) => group.push(index),
+            None => groups.push(vec![index]),
+        }
+    }
+    groups
+}
+
+#[tauri::command]
+pub async fn group_similar_images(
+    app: tauri::AppHandle,
+    dir: String,
+    max_distance: u32,
+) -> Result<Vec<Vec<String>>, String> {
+    let resolved = crate::resolve_scoped_path(&app, &dir)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut files = crate::collect_png_files(&resolved)?;
+        files.sort();
+
+        let hashed = files
+            .par_iter()
+            .filter_map(|file| match difference_hash(file) {
+                Ok(hash) => Some((file.clone(), hash)),
+                Err(error) => {
+                    log::warn!("skipping {} while grouping: {}", file.display(), error);
+                    None
+                }
+            })
+            .collect::<Vec<(std::path::PathBuf, u64)>>();
+
+        let hashes = hashed.iter().map(|(_, hash)| *hash).collect::<Vec<u64>>();
+        let root = crate::project_root();
+        Ok(cluster_by_hash(&hashes, max_distance)
+            .into_iter()
+            .filter(|group| group.len() > 1)
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|index| crate::to_display_path(&hashed[index].0, &root))
+                    .collect()
+            })
+            .collect())
+    })
+    .await
+    .map_err(|error| format!("failed to join group_similar_images task: {}", error))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("dma-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn banded_image(width: u32, height: u32, invert: bool) -> image::RgbImage {
+        image::RgbImage::from_fn(width, height, |x, y| {
+            let band = ((x * 7 / width) + (y * 5 / height)) % 3;
+            let value = [40u8, 200, 120][band as usize];
+            let value = if invert { 255 - value } else { value };
+            image::Rgb([value, value / 2, 255 - value])
+        })
+    }
+
+    #[test]
+    fn re_encoded_images_group_together_and_different_images_do_not() {
+        let dir = scratch_dir("dhash");
+        let original = dir.join("original.png");
+        let re_encoded = dir.join("re-encoded.jpg");
+        let resized = dir.join("resized.png");
+        let different = dir.join("different.png");
+        banded_image(640, 480, false).save(&original).unwrap();
+        image::DynamicImage::ImageRgb8(banded_image(640, 480, false))
+            .save_with_format(&re_encoded, image::ImageFormat::Jpeg)
+            .unwrap();
+        image::imageops::resize(
+            &banded_image(640, 480, false),
+            600,
+            450,
+            image::imageops::FilterType::Triangle,
+        )
+        .save(&resized)
+        .unwrap();
+        banded_image(640, 480, true).save(&different).unwrap();
+
+        let hashes = [&original, &re_encoded, &resized, &different]
+            .iter()
+            .map(|path| difference_hash(path).unwrap())
+            .collect::<Vec<u64>>();
+        assert!((hashes[0] ^ hashes[1]).count_ones() <= 4);
+        assert!((hashes[0] ^ hashes[2]).count_ones() <= 4);
+        assert!((hashes[0] ^ hashes[3]).count_ones() > 16);
+        assert_eq!(cluster_by_hash(&hashes, 8), vec![vec![0, 1, 2], vec![3]]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn grouping_does_not_chain_past_the_threshold() {
+        // Each hash is 3 bits from its neighbour, but the ends are 6 bits apart.
+        let hashes = [0b000000, 0b000111, 0b111111];
+        assert_eq!(cluster_by_hash(&hashes, 3), vec![vec![0, 1], vec![2]]);
+    }
+}
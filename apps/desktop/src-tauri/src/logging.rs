@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+use tauri::Manager;
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind, TimezoneStrategy};
+
+const LOG_FILE_NAME: &str = "dont-mockup-again";
+const MAX_LOG_FILE_BYTES: u128 = 5 * 1024 * 1024;
+const KEPT_LOG_FILES: usize = 5;
+
+pub fn plugin<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R> {
+    tauri_plugin_log::Builder::new()
+        .clear_targets()
+        .targets([
+            Target::new(TargetKind::Stderr),
+            Target::new(TargetKind::LogDir {
+                file_name: Some(LOG_FILE_NAME.to_string()),
+            }),
+        ])
+        .level(log::LevelFilter::Info)
+        .max_file_size(MAX_LOG_FILE_BYTES)
+        .rotation_strategy(RotationStrategy::KeepSome(KEPT_LOG_FILES))
+        .timezone_strategy(TimezoneStrategy::UseLocal)
+        .build()
+}
+
+fn log_dir<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_log_dir()
+        .map_err(|error| format!("failed to resolve app log dir: {}", error))
+}
+
+#[tauri::command]
+pub fn get_log_path(app: tauri::AppHandle) -> Result<String, String> {
+    let path = log_dir(&app)?.join(format!("{}.log", LOG_FILE_NAME));
+    Ok(path.to_string_lossy().replace('\\', "/"))
+}
+
+#[tauri::command]
+pub fn reveal_logs(app: tauri::AppHandle) -> Result<(), String> {
+    let directory = log_dir(&app)?;
+    std::fs::create_dir_all(&directory)
+        .map_err(|error| format!("failed to create log dir: {}", error))?;
+    crate::open_with_system(directory.as_os_str())
+}
@@ -1,11 +1,50 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use base64::{engine::general_purpose::STANDARD, Engine as _};
-use std::collections::BTreeSet;
+mod archive;
+mod asset_protocol;
+mod bundle;
+mod chunked_read;
+mod chunked_write;
+mod clipboard;
+mod config_store;
+mod dir_watch;
+mod display;
+mod drag_drop;
+mod fonts;
+mod images;
+mod logging;
+mod path_scope;
+mod pipeline;
+mod pipeline_settings;
+mod presets;
+mod preview_window;
+mod project_backups;
+mod project_validation;
+mod project_watch;
+mod recent_projects;
+mod render;
+mod scan;
+mod screen_permission;
+mod snapshots;
+mod tail;
+mod temp_dirs;
+mod theme;
+mod thumbnails;
+mod window_state;
+mod zoom;
+
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine as _,
+};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::ffi::{OsStr, OsString};
 use std::fs;
-use std::path::PathBuf;
-use std::process::Command;
-use std::sync::OnceLock;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 use tauri::Manager;
 
 #[cfg(target_os = "macos")]
@@ -17,7 +56,7 @@ fn disable_swipe_navigation<R: tauri::Runtime>(window: &tauri::WebviewWindow<R>)
         let view: &WKWebView = &*webview.inner().cast();
         view.setAllowsBackForwardNavigationGestures(false);
     }) {
-        eprintln!(
+        log::warn!(
             "failed to disable back-forward swipe gestures for webview `{}`: {}",
             window.label(),
             error
@@ -31,7 +70,7 @@ fn disable_swipe_navigation_in_webview<R: tauri::Runtime>(webview: &tauri::Webvi
         let view: &WKWebView = &*platform_webview.inner().cast();
         view.setAllowsBackForwardNavigationGestures(false);
     }) {
-        eprintln!(
+        log::warn!(
             "failed to disable back-forward swipe gestures for webview `{}`: {}",
             webview.label(),
             error
@@ -44,464 +83,1730 @@ fn project_root() -> PathBuf {
     root.canonicalize().unwrap_or(root)
 }
 
-fn resolve_project_path(input: &str) -> PathBuf {
+pub(crate) fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn resolve_with_root(root: &Path, input: &str) -> PathBuf {
     let candidate = PathBuf::from(input);
     if candidate.is_absolute() {
         candidate
     } else {
-        project_root().join(candidate)
+        root.join(candidate)
     }
 }
 
+fn resolve_project_path(input: &str) -> PathBuf {
+    resolve_with_root(&project_root(), input)
+}
+
+fn canonicalize_existing_prefix(path: &Path) -> Result<PathBuf, String> {
+    let invalid = || format!("invalid path: {}", path.display());
+    let mut existing = path;
+    let mut missing = Vec::new();
+    let canonical = loop {
+        match existing.canonicalize() {
+            Ok(canonical) => break canonical,
+            Err(_) => {
+                missing.push(existing.file_name().ok_or_else(invalid)?);
+                existing = existing
+                    .parent()
+                    .filter(|parent| !parent.as_os_str().is_empty())
+                    .unwrap_or_else(|| Path::new("."));
+            }
+        }
+    };
+
+    Ok(missing.into_iter().rev().fold(canonical, |joined, component| joined.join(component)))
+}
+
+// Shared by every traversal guard: the last component is not resolved, so a symlink is judged by
+// where it lives rather than where it points.
+fn contained_path(path: &Path, roots: &[PathBuf]) -> Option<PathBuf> {
+    let file_name = path.file_name()?;
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let candidate = canonicalize_existing_prefix(parent).ok()?.join(file_name);
+    roots
+        .iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .any(|root| candidate.starts_with(&root))
+        .then_some(candidate)
+}
+
+// Reads and writes follow a symlinked leaf, so scope checks judge it by its target. The
+// rename/delete guards act on the link itself and use contained_path directly.
+fn scoped_target(path: &Path, roots: &[PathBuf]) -> Option<PathBuf> {
+    match path.canonicalize() {
+        Ok(canonical) => contained_path(&canonical, roots),
+        Err(_) if fs::symlink_metadata(path).is_ok() => None,
+        Err(_) => contained_path(path, roots),
+    }
+}
+
+fn ensure_within_roots(path: &Path, roots: &[PathBuf]) -> Result<PathBuf, String> {
+    let outside = || format!("path is outside the allowed roots: {}", path.display());
+    let canonical = contained_path(path, roots).ok_or_else(outside)?;
+    let is_root = roots
+        .iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .any(|root| root == canonical);
+    if is_root {
+        return Err(outside());
+    }
+    Ok(canonical)
+}
+
+#[tauri::command]
+fn is_within_project(path: String) -> bool {
+    let root = project_root();
+    contained_path(&resolve_with_root(&root, path.trim()), &[root]).is_some()
+}
+
+fn to_display_path(file: &Path, root: &Path) -> String {
+    file.strip_prefix(root)
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|_| file.to_string_lossy().replace('\\', "/"))
+}
+
+#[tauri::command]
+fn to_project_relative(path: String) -> Option<String> {
+    let root = project_root();
+    let resolved = resolve_with_root(&root, path.trim());
+    resolved
+        .strip_prefix(&root)
+        .ok()
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+}
+
+#[tauri::command]
+fn to_absolute(relative: String) -> String {
+    resolve_project_path(relative.trim()).to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(unix)]
+fn os_str_to_bytes(value: &OsStr) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    value.as_bytes().to_vec()
+}
+
+#[cfg(windows)]
+fn os_str_to_bytes(value: &OsStr) -> Vec<u8> {
+    use std::os::windows::ffi::OsStrExt;
+    value
+        .encode_wide()
+        .flat_map(|unit| unit.to_le_bytes())
+        .collect()
+}
+
+#[cfg(not(any(unix, windows)))]
+fn os_str_to_bytes(value: &OsStr) -> Vec<u8> {
+    value.to_string_lossy().into_owned().into_bytes()
+}
+
+#[cfg(unix)]
+fn bytes_to_os_string(bytes: Vec<u8>) -> Result<OsString, String> {
+    use std::os::unix::ffi::OsStringExt;
+    Ok(OsString::from_vec(bytes))
+}
+
+#[cfg(windows)]
+fn bytes_to_os_string(bytes: Vec<u8>) -> Result<OsString, String> {
+    use std::os::windows::ffi::OsStringExt;
+    if !bytes.len().is_multiple_of(2) {
+        return Err("invalid file id: odd byte length".to_string());
+    }
+
+    let units = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect::<Vec<u16>>();
+    Ok(OsString::from_wide(&units))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn bytes_to_os_string(bytes: Vec<u8>) -> Result<OsString, String> {
+    String::from_utf8(bytes)
+        .map(OsString::from)
+        .map_err(|error| format!("invalid file id: {}", error))
+}
+
+fn encode_file_id(path: &Path) -> String {
+    URL_SAFE_NO_PAD.encode(os_str_to_bytes(path.as_os_str()))
+}
+
+fn resolve_file_id(id: &str) -> Result<PathBuf, String> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(id.as_bytes())
+        .map_err(|error| format!("invalid file id: {}", error))?;
+    bytes_to_os_string(bytes).map(PathBuf::from)
+}
+
 fn resolve_dialog_directory(preferred_dir: Option<String>) -> Option<PathBuf> {
+    resolve_dialog_directory_with_root(&project_root(), preferred_dir)
+}
+
+fn resolve_dialog_directory_with_root(root: &Path, preferred_dir: Option<String>) -> Option<PathBuf> {
     let raw = preferred_dir?;
     let trimmed = raw.trim();
     if trimmed.is_empty() {
         return None;
     }
 
-    let candidate = resolve_project_path(trimmed);
+    let candidate = resolve_with_root(root, trimmed);
     let directory = if candidate.is_file() {
         candidate.parent()?.to_path_buf()
     } else {
         candidate
     };
 
-    if directory.exists() && directory.is_dir() {
-        Some(directory)
-    } else {
-        None
+    if directory.exists() && directory.is_dir() {
+        Some(directory)
+    } else {
+        None
+    }
+}
+
+fn is_png_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|value| value.to_str())
+        .map(|value| value.eq_ignore_ascii_case("png"))
+        .unwrap_or(false)
+}
+
+fn collect_png_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    collect_files_matching(dir, &ScanRules::default(), &is_png_file)
+}
+
+const IGNORED_DIR_NAMES: [&str; 1] = ["node_modules"];
+
+// Hidden directories (including .git) and IGNORED_DIR_NAMES are skipped unless
+// include_ignored_dirs is set. Exclude globs are matched against both the entry name and its path
+// relative to the scan root, and a max_depth of 0 only lists the top-level directory.
+#[derive(Default)]
+struct ScanRules {
+    max_depth: Option<usize>,
+    exclude: Option<globset::GlobSet>,
+    include_ignored_dirs: bool,
+}
+
+impl ScanRules {
+    fn new(max_depth: Option<usize>, exclude: &[String]) -> Result<Self, String> {
+        if exclude.is_empty() {
+            return Ok(Self {
+                max_depth,
+                ..Self::default()
+            });
+        }
+        let mut builder = globset::GlobSetBuilder::new();
+        for pattern in exclude {
+            let glob = globset::Glob::new(pattern.trim()).map_err(|error| format!("invalid exclude pattern {}: {}", pattern, error))?;
+            builder.add(glob);
+        }
+        let exclude = builder.build().map_err(|error| format!("invalid exclude patterns: {}", error))?;
+        Ok(Self {
+            max_depth,
+            exclude: Some(exclude),
+            include_ignored_dirs: false,
+        })
+    }
+
+    fn unfiltered() -> Self {
+        Self {
+            include_ignored_dirs: true,
+            ..Self::default()
+        }
+    }
+
+    fn skips(&self, root: &Path, path: &Path, is_dir: bool) -> bool {
+        let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+        if is_dir && !self.include_ignored_dirs && (name.starts_with('.') || IGNORED_DIR_NAMES.contains(&name.as_ref())) {
+            return true;
+        }
+        let Some(exclude) = &self.exclude else {
+            return false;
+        };
+        exclude.is_match(name.as_ref()) || path.strip_prefix(root).map(|relative| exclude.is_match(relative)).unwrap_or(false)
+    }
+}
+
+fn collect_files_matching(
+    dir: &Path,
+    rules: &ScanRules,
+    matches: &(impl Fn(&Path) -> bool + Sync),
+) -> Result<Vec<PathBuf>, String> {
+    walk_files_matching(dir, dir, 0, rules, matches)
+}
+
+fn walk_files_matching(
+    root: &Path,
+    dir: &Path,
+    depth: usize,
+    rules: &ScanRules,
+    matches: &(impl Fn(&Path) -> bool + Sync),
+) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|error| format!("read_dir failed: {}", error))?
+        .map(|entry| {
+            entry
+                .map(|entry| entry.path())
+                .map_err(|error| format!("read_dir entry failed: {}", error))
+        })
+        .collect::<Result<Vec<PathBuf>, String>>()?;
+
+    let nested = entries
+        .into_par_iter()
+        .map(|path| {
+            if path.is_dir() {
+                if rules.skips(root, &path, true) || rules.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                    Ok(Vec::new())
+                } else {
+                    walk_files_matching(root, &path, depth + 1, rules, matches)
+                }
+            } else if !rules.skips(root, &path, false) && matches(&path) {
+                Ok(vec![path])
+            } else {
+                Ok(Vec::new())
+            }
+        })
+        .collect::<Result<Vec<Vec<PathBuf>>, String>>()?;
+
+    Ok(nested.into_iter().flatten().collect())
+}
+
+const DEFAULT_IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "webp", "heic"];
+
+fn image_format(path: &Path, extensions: &[String]) -> Option<String> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    if !extensions.contains(&extension) {
+        return None;
+    }
+    Some(if extension == "jpg" {
+        "jpeg".to_string()
+    } else {
+        extension
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl TextEncoding {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "utf-8" | "utf8" => Ok(Self::Utf8),
+            "utf-16le" | "utf16le" => Ok(Self::Utf16Le),
+            "utf-16be" | "utf16be" => Ok(Self::Utf16Be),
+            other => Err(format!("unsupported encoding: {}", other)),
+        }
+    }
+
+    fn detect_bom(bytes: &[u8]) -> Option<(Self, usize)> {
+        if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            Some((Self::Utf8, 3))
+        } else if bytes.starts_with(&[0xFF, 0xFE]) {
+            Some((Self::Utf16Le, 2))
+        } else if bytes.starts_with(&[0xFE, 0xFF]) {
+            Some((Self::Utf16Be, 2))
+        } else {
+            None
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], to_unit: fn([u8; 2]) -> u16) -> Result<String, String> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err("invalid UTF-16: odd byte length".to_string());
+    }
+
+    let units = bytes.chunks_exact(2).map(|pair| to_unit([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map_err(|error| format!("invalid UTF-16: {}", error))
+}
+
+fn decode_text(bytes: &[u8], fallback: Option<TextEncoding>) -> Result<String, String> {
+    let (encoding, body) = match TextEncoding::detect_bom(bytes) {
+        Some((encoding, bom_len)) => (encoding, &bytes[bom_len..]),
+        None => (fallback.unwrap_or(TextEncoding::Utf8), bytes),
+    };
+
+    match encoding {
+        TextEncoding::Utf8 => std::str::from_utf8(body)
+            .map(str::to_string)
+            .map_err(|error| format!("invalid UTF-8: {}", error)),
+        TextEncoding::Utf16Le => decode_utf16(body, u16::from_le_bytes),
+        TextEncoding::Utf16Be => decode_utf16(body, u16::from_be_bytes),
+    }
+}
+
+const DEFAULT_TEXT_READ_LIMIT: u64 = 64 * 1024 * 1024;
+
+fn load_text_file(resolved: &Path, encoding: Option<&str>, max_bytes: Option<u64>) -> Result<String, String> {
+    let fallback = encoding.map(TextEncoding::parse).transpose()?;
+    let metadata =
+        fs::metadata(resolved).map_err(|error| format!("failed to read {}: {}", resolved.display(), error))?;
+    if metadata.len() > max_bytes.unwrap_or(DEFAULT_TEXT_READ_LIMIT) {
+        return Err(format!("file too large: {} bytes", metadata.len()));
+    }
+
+    let bytes = fs::read(resolved).map_err(|error| format!("failed to read {}: {}", resolved.display(), error))?;
+    decode_text(&bytes, fallback).map_err(|error| format!("failed to decode {}: {}", resolved.display(), error))
+}
+
+#[tauri::command]
+fn read_text_file(app: tauri::AppHandle, path: String, encoding: Option<String>, max_bytes: Option<u64>) -> Result<String, String> {
+    load_text_file(&resolve_scoped_path(&app, &path)?, encoding.as_deref(), max_bytes)
+}
+
+fn atomic_temp_path(target: &Path) -> Result<PathBuf, String> {
+    static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let parent = target
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("invalid write target: {}", target.display()))?;
+    Ok(parent.join(format!(
+        ".{}.{}-{}.tmp",
+        file_name,
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    )))
+}
+
+fn backup_path(target: &Path) -> PathBuf {
+    let mut backup = target.as_os_str().to_os_string();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+// Copies rather than renames so the target stays in place if the following write fails.
+fn backup_existing_file(target: &Path) -> Result<(), String> {
+    if !target.is_file() {
+        return Ok(());
+    }
+    let backup = backup_path(target);
+    fs::copy(target, &backup)
+        .map(|_| ())
+        .map_err(|error| format!("failed to back up {} to {}: {}", target.display(), backup.display(), error))
+}
+
+fn write_atomically(target: &Path, bytes: &[u8]) -> Result<(), String> {
+    let temp_path = atomic_temp_path(target)?;
+
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)
+            .map_err(|error| format!("failed to create {}: {}", temp_path.display(), error))?;
+        file.write_all(bytes)
+            .map_err(|error| format!("failed to write {}: {}", temp_path.display(), error))?;
+        file.sync_all()
+            .map_err(|error| format!("failed to sync {}: {}", temp_path.display(), error))?;
+        drop(file);
+        fs::rename(&temp_path, target)
+            .map_err(|error| format!("failed to replace {}: {}", target.display(), error))
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+#[derive(Clone, Copy)]
+enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "lf" => Ok(Self::Lf),
+            "crlf" => Ok(Self::Crlf),
+            other => Err(format!("unsupported line ending: {}", other)),
+        }
+    }
+}
+
+fn normalize_line_endings(content: &str, line_ending: LineEnding) -> String {
+    let unified = content.replace("\r\n", "\n").replace('\r', "\n");
+    match line_ending {
+        LineEnding::Lf => unified,
+        LineEnding::Crlf => unified.replace('\n', "\r\n"),
+    }
+}
+
+#[tauri::command]
+fn write_text_file(
+    app: tauri::AppHandle,
+    project_watch: tauri::State<'_, project_watch::ProjectWatch>,
+    path: String,
+    content: String,
+    line_ending: Option<String>,
+    keep_backup: Option<bool>,
+) -> Result<(), String> {
+    let resolved = resolve_scoped_path(&app, &path)?;
+    let content = match line_ending.as_deref().map(LineEnding::parse).transpose()? {
+        Some(line_ending) => normalize_line_endings(&content, line_ending),
+        None => content,
+    };
+
+    if let Some(parent) = resolved.parent() {
+        fs::create_dir_all(parent).map_err(|error| format!("failed to create parent dirs: {}", error))?;
+    }
+    if keep_backup.unwrap_or(false) {
+        backup_existing_file(&resolved)?;
+    }
+    if let Err(error) = project_backups::backup_before_write(&resolved) {
+        log::warn!("failed to back up project file {}: {}", resolved.display(), error);
+    }
+
+    project_watch.note_own_write(&resolved);
+    write_atomically(&resolved, content.as_bytes())
+}
+
+#[tauri::command]
+fn read_json(app: tauri::AppHandle, path: String) -> Result<serde_json::Value, String> {
+    let resolved = resolve_scoped_path(&app, &path)?;
+    let raw = load_text_file(&resolved, None, None)?;
+    serde_json::from_str(&raw).map_err(|error| format!("failed to parse {}: {}", resolved.display(), error))
+}
+
+fn serialize_json(value: &serde_json::Value, pretty: bool) -> Result<String, String> {
+    let serialized = if pretty {
+        serde_json::to_string_pretty(value).map(|text| text + "\n")
+    } else {
+        serde_json::to_string(value)
+    };
+    serialized.map_err(|error| format!("failed to serialize json: {}", error))
+}
+
+#[tauri::command]
+fn write_json(
+    app: tauri::AppHandle,
+    project_watch: tauri::State<'_, project_watch::ProjectWatch>,
+    path: String,
+    value: serde_json::Value,
+    pretty: bool,
+) -> Result<(), String> {
+    let resolved = resolve_scoped_path(&app, &path)?;
+    let serialized = serialize_json(&value, pretty)?;
+
+    if let Some(parent) = resolved.parent() {
+        fs::create_dir_all(parent).map_err(|error| format!("failed to create parent dirs: {}", error))?;
+    }
+
+    project_watch.note_own_write(&resolved);
+    write_atomically(&resolved, serialized.as_bytes())
+}
+
+#[derive(Debug, Serialize)]
+struct ListedFile {
+    path: String,
+    id: String,
+}
+
+#[tauri::command]
+async fn list_png_files(
+    app: tauri::AppHandle,
+    path: String,
+    max_depth: Option<usize>,
+    exclude: Option<Vec<String>>,
+) -> Result<Vec<ListedFile>, String> {
+    let resolved = resolve_scoped_path(&app, &path)?;
+    let rules = ScanRules::new(max_depth, &exclude.unwrap_or_default())?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        if !resolved.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut files = collect_files_matching(&resolved, &rules, &is_png_file)?;
+        files.sort();
+
+        let root = project_root();
+        let results = files
+            .into_iter()
+            .map(|file| ListedFile {
+                path: to_display_path(&file, &root),
+                id: encode_file_id(&file),
+            })
+            .collect::<Vec<ListedFile>>();
+
+        Ok(results)
+    })
+    .await
+    .map_err(|error| format!("failed to join list_png_files task: {}", error))?
+}
+
+#[derive(Serialize)]
+struct ListedImage {
+    path: String,
+    id: String,
+    format: String,
+}
+
+#[tauri::command]
+fn list_image_files(
+    app: tauri::AppHandle,
+    path: String,
+    extensions: Option<Vec<String>>,
+) -> Result<Vec<ListedImage>, String> {
+    let resolved = resolve_scoped_path(&app, &path)?;
+    if !resolved.exists() {
+        return Ok(Vec::new());
+    }
+
+    let extensions = match extensions {
+        Some(extensions) => extensions
+            .iter()
+            .map(|extension| extension.trim().trim_start_matches('.').to_ascii_lowercase())
+            .filter(|extension| !extension.is_empty())
+            .collect::<Vec<String>>(),
+        None => DEFAULT_IMAGE_EXTENSIONS
+            .iter()
+            .map(|extension| extension.to_string())
+            .collect(),
+    };
+    let mut files = collect_files_matching(&resolved, &ScanRules::unfiltered(), &|file: &Path| {
+        image_format(file, &extensions).is_some()
+    })?;
+    files.sort();
+
+    let root = project_root();
+    let results = files
+        .into_iter()
+        .filter_map(|file| {
+            Some(ListedImage {
+                format: image_format(&file, &extensions)?,
+                path: to_display_path(&file, &root),
+                id: encode_file_id(&file),
+            })
+        })
+        .collect::<Vec<ListedImage>>();
+
+    Ok(results)
+}
+
+fn has_glob_syntax(component: &str) -> bool {
+    component.contains(['*', '?', '[', '{'])
+}
+
+// Only the directory below the literal prefix has to be walked, e.g. `screenshots/` for
+// `screenshots/**/iphone-*.png`.
+fn split_glob_pattern(pattern: &str) -> (String, String) {
+    let components = pattern.split('/').collect::<Vec<&str>>();
+    let literal = components
+        .iter()
+        .position(|component| has_glob_syntax(component))
+        .unwrap_or(components.len());
+    (components[..literal].join("/"), components[literal..].join("/"))
+}
+
+#[tauri::command]
+async fn glob_files(app: tauri::AppHandle, pattern: String) -> Result<Vec<ListedFile>, String> {
+    let (prefix, rest) = split_glob_pattern(pattern.trim());
+    let base = resolve_scoped_path(&app, &prefix)?;
+    if rest.is_empty() {
+        return Ok(if base.is_file() {
+            vec![ListedFile {
+                path: to_display_path(&base, &project_root()),
+                id: encode_file_id(&base),
+            }]
+        } else {
+            Vec::new()
+        });
+    }
+    let matcher = globset::GlobBuilder::new(&rest)
+        .literal_separator(true)
+        .build()
+        .map_err(|error| format!("invalid glob pattern {}: {}", pattern, error))?
+        .compile_matcher();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        if !base.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut files = collect_files_matching(&base, &ScanRules::unfiltered(), &|file: &Path| {
+            file.strip_prefix(&base)
+                .map(|relative| matcher.is_match(relative))
+                .unwrap_or(false)
+        })?;
+        files.sort();
+
+        let root = project_root();
+        Ok(files
+            .into_iter()
+            .map(|file| ListedFile {
+                path: to_display_path(&file, &root),
+                id: encode_file_id(&file),
+            })
+            .collect())
+    })
+    .await
+    .map_err(|error| format!("failed to join glob_files task: {}", error))?
+}
+
+#[tauri::command]
+async fn read_file_base64(app: tauri::AppHandle, path: String, file_id: Option<String>) -> Result<String, String> {
+    let resolved = match file_id {
+        Some(id) => resolve_file_id(&id)?,
+        None => resolve_project_path(&path),
+    };
+    ensure_in_scope(&app, &resolved)?;
+
+    tauri::async_runtime::spawn_blocking(move || encode_file_base64(&resolved))
+        .await
+        .map_err(|error| format!("failed to join read_file_base64 task: {}", error))?
+}
+
+fn encode_file_base64(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+    Ok(STANDARD.encode(bytes))
+}
+
+#[derive(Serialize)]
+struct FileRange {
+    data: String,
+    total_size: u64,
+}
+
+#[tauri::command]
+fn read_file_range(app: tauri::AppHandle, path: String, offset: u64, len: usize) -> Result<FileRange, String> {
+    let resolved = resolve_scoped_path(&app, &path)?;
+    let mut file = fs::File::open(&resolved).map_err(|error| format!("failed to open {}: {}", resolved.display(), error))?;
+    let total_size = file
+        .metadata()
+        .map_err(|error| format!("failed to stat {}: {}", resolved.display(), error))?
+        .len();
+
+    let mut bytes = Vec::new();
+    if offset < total_size {
+        file.seek(SeekFrom::Start(offset))
+            .map_err(|error| format!("failed to seek {}: {}", resolved.display(), error))?;
+        file.take(len as u64)
+            .read_to_end(&mut bytes)
+            .map_err(|error| format!("failed to read {}: {}", resolved.display(), error))?;
+    }
+
+    Ok(FileRange {
+        data: STANDARD.encode(bytes),
+        total_size,
+    })
+}
+
+const MIME_SNIFF_BYTES: u64 = 8192;
+
+fn looks_like_json(bytes: &[u8]) -> bool {
+    let text = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    matches!(
+        text.iter().find(|byte| !byte.is_ascii_whitespace()),
+        Some(b'{') | Some(b'[')
+    )
+}
+
+fn read_sniff_head(path: &Path) -> Result<Vec<u8>, String> {
+    let file = fs::File::open(path).map_err(|error| format!("failed to open {}: {}", path.display(), error))?;
+    let mut head = Vec::new();
+    file.take(MIME_SNIFF_BYTES)
+        .read_to_end(&mut head)
+        .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+    Ok(head)
+}
+
+#[tauri::command]
+fn detect_mime(app: tauri::AppHandle, path: String) -> Result<String, String> {
+    let head = read_sniff_head(&resolve_scoped_path(&app, &path)?)?;
+    Ok(sniff_mime(&head))
+}
+
+fn sniff_mime(head: &[u8]) -> String {
+    if let Some(kind) = infer::get(head) {
+        if kind.matcher_type() == infer::MatcherType::Font {
+            return format!("font/{}", kind.extension());
+        }
+        return kind.mime_type().to_string();
+    }
+    if looks_like_json(head) {
+        return "application/json".to_string();
+    }
+    "application/octet-stream".to_string()
+}
+
+#[derive(Serialize)]
+struct FileReadResult {
+    path: String,
+    data: Option<String>,
+    error: Option<String>,
+}
+
+#[tauri::command]
+async fn read_files_base64(app: tauri::AppHandle, paths: Vec<String>) -> Vec<FileReadResult> {
+    let tasks = paths
+        .into_iter()
+        .map(|path| {
+            let target = path.clone();
+            let app = app.clone();
+            let task = tauri::async_runtime::spawn_blocking(move || {
+                let resolved = resolve_scoped_path(&app, &target)?;
+                encode_file_base64(&resolved)
+            });
+            (path, task)
+        })
+        .collect::<Vec<_>>();
+
+    let mut results = Vec::with_capacity(tasks.len());
+    for (path, task) in tasks {
+        let outcome = task
+            .await
+            .unwrap_or_else(|error| Err(format!("failed to join read task: {}", error)));
+        results.push(match outcome {
+            Ok(data) => FileReadResult {
+                path,
+                data: Some(data),
+                error: None,
+            },
+            Err(error) => FileReadResult {
+                path,
+                data: None,
+                error: Some(error),
+            },
+        });
+    }
+
+    results
+}
+
+#[tauri::command]
+async fn write_file_base64(app: tauri::AppHandle, path: String, data_base64: String, keep_backup: Option<bool>) -> Result<(), String> {
+    let resolved = resolve_scoped_path(&app, &path)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Some(parent) = resolved.parent() {
+            fs::create_dir_all(parent).map_err(|error| format!("failed to create parent dirs: {}", error))?;
+        }
+
+        let bytes = STANDARD
+            .decode(data_base64.as_bytes())
+            .map_err(|error| format!("failed to decode base64: {}", error))?;
+
+        if keep_backup.unwrap_or(false) {
+            backup_existing_file(&resolved)?;
+        }
+        write_atomically(&resolved, &bytes)
+    })
+    .await
+    .map_err(|error| format!("failed to join write_file_base64 task: {}", error))?
+}
+
+fn allowed_roots(app: &tauri::AppHandle) -> Vec<PathBuf> {
+    let mut roots = vec![project_root()];
+    if let Ok(export_dir) = default_export_dir(app) {
+        roots.push(export_dir);
+    }
+    roots.extend(path_scope::granted_roots(app));
+    roots
+}
+
+fn ensure_in_scope(app: &tauri::AppHandle, path: &Path) -> Result<(), String> {
+    scoped_target(path, &allowed_roots(app))
+        .map(|_| ())
+        .ok_or_else(|| format!("path is outside the allowed roots: {}", path.display()))
+}
+
+fn resolve_scoped_path(app: &tauri::AppHandle, input: &str) -> Result<PathBuf, String> {
+    let resolved = resolve_project_path(input);
+    ensure_in_scope(app, &resolved)?;
+    Ok(resolved)
+}
+
+#[derive(Serialize)]
+struct DeleteResult {
+    path: String,
+    ok: bool,
+    error: Option<String>,
+}
+
+fn delete_within_roots(path: &str, roots: &[PathBuf], to_trash: bool, recursive: bool) -> Result<(), String> {
+    let target = ensure_within_roots(&resolve_project_path(path), roots)?;
+    let is_dir = fs::symlink_metadata(&target)
+        .map_err(|error| format!("failed to stat {}: {}", target.display(), error))?
+        .is_dir();
+    if is_dir && !recursive {
+        let empty = fs::read_dir(&target)
+            .map_err(|error| format!("failed to read dir {}: {}", target.display(), error))?
+            .next()
+            .is_none();
+        if !empty {
+            return Err(format!("directory is not empty: {}", target.display()));
+        }
+    }
+
+    if to_trash {
+        trash::delete(&target).map_err(|error| format!("failed to move {} to trash: {}", target.display(), error))
+    } else if is_dir {
+        fs::remove_dir_all(&target).map_err(|error| format!("failed to delete {}: {}", target.display(), error))
+    } else {
+        fs::remove_file(&target).map_err(|error| format!("failed to delete {}: {}", target.display(), error))
+    }
+}
+
+#[tauri::command]
+fn delete_paths(app: tauri::AppHandle, paths: Vec<String>, to_trash: bool, recursive: Option<bool>) -> Vec<DeleteResult> {
+    let roots = allowed_roots(&app);
+    let recursive = recursive.unwrap_or(false);
+
+    paths
+        .into_iter()
+        .map(|path| match delete_within_roots(&path, &roots, to_trash, recursive) {
+            Ok(()) => DeleteResult {
+                path,
+                ok: true,
+                error: None,
+            },
+            Err(error) => DeleteResult {
+                path,
+                ok: false,
+                error: Some(error),
+            },
+        })
+        .collect()
+}
+
+#[tauri::command]
+fn delete_path(app: tauri::AppHandle, path: String, to_trash: Option<bool>, recursive: Option<bool>) -> Result<(), String> {
+    delete_within_roots(&path, &allowed_roots(&app), to_trash.unwrap_or(true), recursive.unwrap_or(false))
+}
+
+#[tauri::command]
+fn move_path(app: tauri::AppHandle, from: String, to: String, overwrite: bool) -> Result<String, String> {
+    let roots = allowed_roots(&app);
+    let source = ensure_within_roots(&resolve_project_path(from.trim()), &roots)?;
+    let destination = ensure_within_roots(&resolve_project_path(to.trim()), &roots)?;
+    let source_metadata = fs::symlink_metadata(&source).map_err(|error| format!("failed to stat {}: {}", source.display(), error))?;
+
+    if let Ok(existing) = fs::symlink_metadata(&destination) {
+        if !overwrite {
+            return Err(format!("destination already exists: {}", destination.display()));
+        }
+        if existing.is_dir() {
+            return Err(format!("refusing to overwrite directory: {}", destination.display()));
+        }
+    }
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|error| format!("failed to create parent dirs: {}", error))?;
+    }
+
+    match fs::rename(&source, &destination) {
+        Ok(()) => {}
+        // Renames cannot cross volumes (e.g. project root to an external export drive), so files fall back to copy + delete.
+        Err(error) if error.kind() == std::io::ErrorKind::CrossesDevices && source_metadata.is_file() => {
+            fs::copy(&source, &destination).map_err(|error| format!("failed to copy {} to {}: {}", source.display(), destination.display(), error))?;
+            fs::remove_file(&source).map_err(|error| format!("failed to remove {}: {}", source.display(), error))?;
+        }
+        Err(error) => return Err(format!("failed to move {} to {}: {}", source.display(), destination.display(), error)),
+    }
+    Ok(to_display_path(&destination, &project_root()))
+}
+
+fn prune_empty_children(dir: &Path, pruned: &mut Vec<String>) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+
+    let mut empty = true;
+    for entry in entries {
+        let Ok(entry) = entry else {
+            empty = false;
+            continue;
+        };
+        let is_dir = entry.file_type().map(|file_type| file_type.is_dir()).unwrap_or(false);
+        if !is_dir {
+            empty = false;
+            continue;
+        }
+
+        let child = entry.path();
+        if prune_empty_children(&child, pruned) && fs::remove_dir(&child).is_ok() {
+            pruned.push(child.to_string_lossy().replace('\\', "/"));
+        } else {
+            empty = false;
+        }
+    }
+    empty
+}
+
+#[tauri::command]
+fn prune_empty_dirs(app: tauri::AppHandle, root: String) -> Result<Vec<String>, String> {
+    let requested = resolve_project_path(root.trim());
+    let canonical = requested
+        .canonicalize()
+        .map_err(|error| format!("failed to resolve {}: {}", requested.display(), error))?;
+    let roots = allowed_roots(&app);
+    let is_allowed_root = roots
+        .iter()
+        .filter_map(|allowed| allowed.canonicalize().ok())
+        .any(|allowed| allowed == canonical);
+    if !is_allowed_root {
+        ensure_within_roots(&canonical, &roots)?;
+    }
+    if !canonical.is_dir() {
+        return Err(format!("not a directory: {}", canonical.display()));
+    }
+
+    let mut pruned = Vec::new();
+    prune_empty_children(&canonical, &mut pruned);
+    Ok(pruned)
+}
+
+#[derive(Default, Serialize)]
+struct DirectorySize {
+    total_bytes: u64,
+    file_count: u64,
+}
+
+// Symlinks are never followed, so a link pointing back up the tree cannot loop the walk.
+fn measure_directory(dir: &Path) -> DirectorySize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return DirectorySize::default();
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .collect::<Vec<fs::DirEntry>>()
+        .into_par_iter()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => measure_directory(&entry.path()),
+            Ok(file_type) if file_type.is_file() => DirectorySize {
+                total_bytes: entry.metadata().map(|metadata| metadata.len()).unwrap_or(0),
+                file_count: 1,
+            },
+            _ => DirectorySize::default(),
+        })
+        .reduce(DirectorySize::default, |left, right| DirectorySize {
+            total_bytes: left.total_bytes + right.total_bytes,
+            file_count: left.file_count + right.file_count,
+        })
+}
+
+#[tauri::command]
+async fn directory_size(app: tauri::AppHandle, path: String) -> Result<DirectorySize, String> {
+    let resolved = resolve_scoped_path(&app, path.trim())?;
+    if !resolved.is_dir() {
+        return Err(format!("not a directory: {}", resolved.display()));
     }
+
+    tauri::async_runtime::spawn_blocking(move || measure_directory(&resolved))
+        .await
+        .map_err(|error| format!("failed to join directory_size task: {}", error))
 }
 
-fn collect_png_files(dir: &PathBuf, acc: &mut Vec<PathBuf>) -> Result<(), String> {
-    let entries = fs::read_dir(dir).map_err(|error| format!("read_dir failed: {}", error))?;
-    for entry in entries {
-        let entry = entry.map_err(|error| format!("read_dir entry failed: {}", error))?;
-        let path = entry.path();
-        if path.is_dir() {
-            collect_png_files(&path, acc)?;
-            continue;
-        }
+#[derive(Serialize)]
+struct FileStat {
+    size: u64,
+    modified_ms: Option<u64>,
+    is_dir: bool,
+    width: Option<u32>,
+    height: Option<u32>,
+}
 
-        let is_png = path
-            .extension()
-            .and_then(|value| value.to_str())
-            .map(|value| value.eq_ignore_ascii_case("png"))
-            .unwrap_or(false);
+#[tauri::command]
+fn stat_file(app: tauri::AppHandle, path: String) -> Result<FileStat, String> {
+    let resolved = resolve_scoped_path(&app, path.trim())?;
+    let metadata = fs::metadata(&resolved).map_err(|error| format!("failed to stat {}: {}", resolved.display(), error))?;
+    let modified_ms = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as u64);
+    // Non-image files simply come back without dimensions.
+    let dimensions = metadata
+        .is_file()
+        .then(|| images::read_header_dimensions(&resolved).ok())
+        .flatten();
+
+    Ok(FileStat {
+        size: metadata.len(),
+        modified_ms,
+        is_dir: metadata.is_dir(),
+        width: dimensions.map(|(width, _)| width),
+        height: dimensions.map(|(_, height)| height),
+    })
+}
 
-        if is_png {
-            acc.push(path);
-        }
+const EXPORT_SETTINGS_FILE: &str = "export-settings.json";
+
+#[derive(Default, Deserialize, Serialize)]
+struct ExportSettings {
+    default_export_dir: Option<String>,
+}
+
+fn default_export_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let settings = config_store::load_json::<_, ExportSettings>(app, EXPORT_SETTINGS_FILE).unwrap_or_default();
+    if let Some(configured) = settings.default_export_dir.filter(|value| !value.trim().is_empty()) {
+        return Ok(PathBuf::from(configured));
     }
 
-    Ok(())
+    let home_dir = app
+        .path()
+        .home_dir()
+        .map_err(|error| format!("failed to resolve home dir: {}", error))?;
+    Ok(home_dir.join("Don't Mockup Again"))
 }
 
-fn fallback_font_list() -> Vec<String> {
-    [
-        "SF Pro",
-        "SF Pro Display",
-        "SF Pro Text",
-        "Apple SD Gothic Neo",
-        "Helvetica Neue",
-        "Arial",
-        "Noto Sans",
-        "Roboto",
-        "Inter",
-    ]
-    .iter()
-    .map(|item| item.to_string())
-    .collect()
+#[tauri::command]
+fn get_default_export_dir(app: tauri::AppHandle) -> Result<String, String> {
+    let default_dir = default_export_dir(&app)?;
+
+    fs::create_dir_all(&default_dir)
+        .map_err(|error| format!("failed to create export dir {}: {}", default_dir.display(), error))?;
+    Ok(default_dir.to_string_lossy().replace('\\', "/"))
 }
 
-fn normalize_font_list(fonts: Vec<String>) -> Vec<String> {
-    let mut unique = BTreeSet::new();
-    for font in fonts {
-        let trimmed = font.trim();
-        if !trimmed.is_empty() {
-            unique.insert(trimmed.to_string());
-        }
-    }
+#[tauri::command]
+fn set_default_export_dir(app: tauri::AppHandle, path: Option<String>) -> Result<(), String> {
+    // The export dir is itself an allowed root, so only folders already in scope can become it.
+    let default_export_dir = path
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(|value| resolve_scoped_path(&app, &value))
+        .transpose()?
+        .map(|resolved| resolved.to_string_lossy().replace('\\', "/"));
+    config_store::save_json(&app, EXPORT_SETTINGS_FILE, &ExportSettings { default_export_dir })
+}
 
-    if unique.is_empty() {
-        return fallback_font_list();
+fn numbered_file_name(file_name: &OsStr, attempt: u32) -> OsString {
+    if attempt < 2 {
+        return file_name.to_os_string();
     }
 
-    unique.into_iter().collect()
+    let path = Path::new(file_name);
+    let stem = path.file_stem().unwrap_or(file_name).to_string_lossy();
+    match path.extension() {
+        Some(extension) => format!("{} ({}).{}", stem, attempt, extension.to_string_lossy()).into(),
+        None => format!("{} ({})", stem, attempt).into(),
+    }
 }
 
-fn collect_system_fonts() -> Result<Vec<String>, String> {
-    #[cfg(target_os = "macos")]
-    {
-        let output = Command::new("system_profiler")
-            .args(["SPFontsDataType", "-detailLevel", "mini"])
-            .output()
-            .map_err(|error| format!("failed to execute system_profiler: {}", error))?;
-
-        if !output.status.success() {
-            return Err(String::from_utf8_lossy(&output.stderr).to_string());
-        }
+#[tauri::command]
+fn export_file(app: tauri::AppHandle, src: String, export_dir: String) -> Result<String, String> {
+    let source = resolve_scoped_path(&app, src.trim())?;
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| format!("invalid source file: {}", source.display()))?;
+    let directory = if export_dir.trim().is_empty() {
+        default_export_dir(&app)?
+    } else {
+        resolve_scoped_path(&app, export_dir.trim())?
+    };
+    fs::create_dir_all(&directory).map_err(|error| format!("failed to create export dir: {}", error))?;
 
-        let raw = String::from_utf8_lossy(&output.stdout);
-        let mut fonts = Vec::new();
-        for line in raw.lines() {
-            let trimmed = line.trim();
-            if let Some(name) = trimmed.strip_prefix("Full Name:") {
-                fonts.push(name.trim().to_string());
-                continue;
-            }
+    let target = copy_to_unique_file(&source, file_name, &directory)?;
+    Ok(target.to_string_lossy().replace('\\', "/"))
+}
 
-            if let Some(name) = trimmed.strip_prefix("Family:") {
-                fonts.push(name.trim().to_string());
-            }
+fn copy_to_unique_file(source: &Path, file_name: &OsStr, directory: &Path) -> Result<PathBuf, String> {
+    let mut input = fs::File::open(source).map_err(|error| format!("failed to open {}: {}", source.display(), error))?;
+    let mut attempt = 1;
+    loop {
+        let target = directory.join(numbered_file_name(file_name, attempt));
+        attempt += 1;
+        let mut output = match fs::OpenOptions::new().write(true).create_new(true).open(&target) {
+            Ok(output) => output,
+            Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(error) => return Err(format!("failed to create {}: {}", target.display(), error)),
+        };
+
+        if let Err(error) = std::io::copy(&mut input, &mut output) {
+            let _ = fs::remove_file(&target);
+            return Err(format!("failed to write {}: {}", target.display(), error));
         }
+        return Ok(target);
+    }
+}
 
-        return Ok(normalize_font_list(fonts));
+#[tauri::command]
+fn import_asset(app: tauri::AppHandle, src: String, subdir: Option<String>) -> Result<String, String> {
+    let source = PathBuf::from(src.trim());
+    ensure_in_scope(&app, &source)?;
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| format!("invalid source file: {}", source.display()))?;
+    if !infer::is_image(&read_sniff_head(&source)?) {
+        return Err(format!("not an image file: {}", source.display()));
     }
 
-    #[cfg(target_os = "linux")]
-    {
-        let output = Command::new("fc-list")
-            .args([":", "family"])
-            .output()
-            .map_err(|error| format!("failed to execute fc-list: {}", error))?;
+    let root = project_root();
+    let subdir = subdir
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or("assets");
+    let directory = ensure_within_roots(&resolve_with_root(&root, subdir), std::slice::from_ref(&root))?;
+    fs::create_dir_all(&directory).map_err(|error| format!("failed to create {}: {}", directory.display(), error))?;
 
-        if !output.status.success() {
-            return Err(String::from_utf8_lossy(&output.stderr).to_string());
-        }
+    let target = copy_to_unique_file(&source, file_name, &directory)?;
+    Ok(to_display_path(&target, &root))
+}
 
-        let raw = String::from_utf8_lossy(&output.stdout);
-        let mut fonts = Vec::new();
-        for line in raw.lines() {
-            let families = line.split(':').next_back().unwrap_or(line);
-            for family in families.split(',') {
-                fonts.push(family.trim().to_string());
-            }
-        }
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ConflictPolicy {
+    Rename,
+    Skip,
+    Overwrite,
+}
 
-        return Ok(normalize_font_list(fonts));
-    }
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CopyStatus {
+    Copied,
+    Skipped,
+    Failed,
+}
 
-    #[cfg(target_os = "windows")]
-    {
-        let output = Command::new("powershell")
-            .args([
-                "-NoProfile",
-                "-Command",
-                "Get-ItemProperty 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Fonts' | Select-Object -Property * -ExcludeProperty PS* | ForEach-Object { $_.PSObject.Properties.Name }",
-            ])
-            .output()
-            .map_err(|error| format!("failed to execute powershell: {}", error))?;
-
-        if !output.status.success() {
-            return Err(String::from_utf8_lossy(&output.stderr).to_string());
-        }
+#[derive(Serialize)]
+struct CopyResult {
+    source: String,
+    target: Option<String>,
+    status: CopyStatus,
+    error: Option<String>,
+}
+
+fn preserve_modified_time(source: &Path, target: &Path) -> Result<(), String> {
+    let modified = fs::metadata(source)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|error| format!("failed to stat {}: {}", source.display(), error))?;
+    fs::OpenOptions::new()
+        .write(true)
+        .open(target)
+        .and_then(|file| file.set_modified(modified))
+        .map_err(|error| format!("failed to set mtime on {}: {}", target.display(), error))
+}
 
-        let raw = String::from_utf8_lossy(&output.stdout);
-        let mut fonts = Vec::new();
-        for line in raw.lines() {
-            let cleaned = line
-                .replace('\u{feff}', "")
-                .split('(')
-                .next()
-                .unwrap_or("")
-                .trim()
-                .to_string();
-
-            if !cleaned.is_empty() {
-                fonts.push(cleaned);
+fn copy_with_policy(source: &Path, directory: &Path, on_conflict: ConflictPolicy) -> Result<Option<PathBuf>, String> {
+    if !source.is_file() {
+        return Err(format!("not a file: {}", source.display()));
+    }
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| format!("invalid source file: {}", source.display()))?;
+    let target = directory.join(file_name);
+
+    let target = match on_conflict {
+        ConflictPolicy::Rename => copy_to_unique_file(source, file_name, directory)?,
+        ConflictPolicy::Skip if target.exists() => return Ok(None),
+        ConflictPolicy::Skip | ConflictPolicy::Overwrite => {
+            if target.is_dir() {
+                return Err(format!("destination is a directory: {}", target.display()));
+            }
+            let temp_path = atomic_temp_path(&target)?;
+            let copied = fs::copy(source, &temp_path)
+                .map_err(|error| format!("failed to copy {}: {}", source.display(), error))
+                .and_then(|_| {
+                    fs::rename(&temp_path, &target).map_err(|error| format!("failed to replace {}: {}", target.display(), error))
+                });
+            if let Err(error) = copied {
+                let _ = fs::remove_file(&temp_path);
+                return Err(error);
             }
+            target
         }
+    };
+    preserve_modified_time(source, &target)?;
+    Ok(Some(target))
+}
 
-        return Ok(normalize_font_list(fonts));
-    }
+#[tauri::command]
+fn copy_files(
+    app: tauri::AppHandle,
+    sources: Vec<String>,
+    destination_dir: String,
+    on_conflict: ConflictPolicy,
+) -> Result<Vec<CopyResult>, String> {
+    let root = project_root();
+    let requested = resolve_project_path(destination_dir.trim());
+    let directory = scoped_target(&requested, &allowed_roots(&app))
+        .ok_or_else(|| format!("path is outside the allowed roots: {}", requested.display()))?;
+    fs::create_dir_all(&directory).map_err(|error| format!("failed to create {}: {}", directory.display(), error))?;
 
-    #[allow(unreachable_code)]
-    Ok(fallback_font_list())
+    let results = sources
+        .into_iter()
+        .map(|source| match resolve_scoped_path(&app, source.trim()).and_then(|resolved| copy_with_policy(&resolved, &directory, on_conflict)) {
+            Ok(Some(target)) => CopyResult {
+                source,
+                target: Some(to_display_path(&target, &root)),
+                status: CopyStatus::Copied,
+                error: None,
+            },
+            Ok(None) => CopyResult {
+                source,
+                target: None,
+                status: CopyStatus::Skipped,
+                error: None,
+            },
+            Err(error) => CopyResult {
+                source,
+                target: None,
+                status: CopyStatus::Failed,
+                error: Some(error),
+            },
+        })
+        .collect();
+    Ok(results)
 }
 
-struct PipelineRuntime {
-    node_cmd: PathBuf,
-    script_path: PathBuf,
-    use_tsx_loader: bool,
-    working_dir: PathBuf,
+const RENAMEABLE_IMAGE_EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "webp"];
+
+fn is_renameable_image(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|value| value.to_str())
+            .map(|value| RENAMEABLE_IMAGE_EXTENSIONS.iter().any(|accepted| value.eq_ignore_ascii_case(accepted)))
+            .unwrap_or(false)
 }
 
-fn resolve_pipeline_working_dir(args: &[String]) -> PathBuf {
-    if let Some(first) = args.first() {
-        let candidate = PathBuf::from(first);
-        if candidate.is_absolute() {
-            if candidate.is_dir() {
-                return candidate;
+// Orders `shot_2` before `shot_10` so indices follow render order rather than string order.
+fn natural_cmp(original_left: &str, original_right: &str) -> std::cmp::Ordering {
+    let (mut left, mut right) = (original_left, original_right);
+    loop {
+        let (Some(a), Some(b)) = (left.chars().next(), right.chars().next()) else {
+            return left.len().cmp(&right.len()).then_with(|| original_left.cmp(original_right));
+        };
+        if a.is_ascii_digit() && b.is_ascii_digit() {
+            let left_end = left.find(|character: char| !character.is_ascii_digit()).unwrap_or(left.len());
+            let right_end = right.find(|character: char| !character.is_ascii_digit()).unwrap_or(right.len());
+            let (left_digits, right_digits) = (left[..left_end].trim_start_matches('0'), right[..right_end].trim_start_matches('0'));
+            let ordering = left_digits.len().cmp(&right_digits.len()).then_with(|| left_digits.cmp(right_digits));
+            if ordering != std::cmp::Ordering::Equal {
+                return ordering;
             }
-            if let Some(parent) = candidate.parent() {
-                return parent.to_path_buf();
+            left = &left[left_end..];
+            right = &right[right_end..];
+        } else {
+            if a != b {
+                return a.cmp(&b);
             }
+            left = &left[a.len_utf8()..];
+            right = &right[b.len_utf8()..];
         }
     }
+}
 
-    std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/"))
-}
-
-fn resolve_pipeline_runtime(app: &tauri::AppHandle, args: &[String]) -> PipelineRuntime {
-    if let Ok(resource_dir) = app.path().resource_dir() {
-        let node_candidates = [
-            resource_dir.join("bin").join("node"),
-            resource_dir.join("resources").join("bin").join("node"),
-        ];
-        let pipeline_candidates = [
-            resource_dir.join("pipeline.bundle.mjs"),
-            resource_dir.join("resources").join("pipeline.bundle.mjs"),
-        ];
-
-        for bundled_node in node_candidates {
-            for bundled_pipeline in &pipeline_candidates {
-                if bundled_node.exists() && bundled_pipeline.exists() {
-                    return PipelineRuntime {
-                        node_cmd: bundled_node.clone(),
-                        script_path: bundled_pipeline.clone(),
-                        use_tsx_loader: false,
-                        working_dir: resolve_pipeline_working_dir(args),
-                    };
-                }
+fn expand_rename_pattern(pattern: &str, index: u32, name: &str) -> Result<String, String> {
+    let mut expanded = String::new();
+    let mut rest = pattern;
+    while let Some(open) = rest.find('{') {
+        expanded.push_str(&rest[..open]);
+        let close = rest[open..]
+            .find('}')
+            .map(|offset| open + offset)
+            .ok_or_else(|| format!("unclosed placeholder in pattern: {}", pattern))?;
+        let placeholder = &rest[open + 1..close];
+        match placeholder.split_once(':') {
+            None if placeholder == "index" => expanded.push_str(&index.to_string()),
+            None if placeholder == "name" => expanded.push_str(name),
+            Some(("index", width)) if !width.is_empty() && width.chars().all(|character| character.is_ascii_digit()) => {
+                let width = width.parse::<usize>().map_err(|_| format!("invalid index width: {}", width))?;
+                expanded.push_str(&format!("{:0width$}", index, width = width));
             }
+            _ => return Err(format!("unsupported placeholder: {{{}}}", placeholder)),
         }
+        rest = &rest[close + 1..];
     }
+    expanded.push_str(rest);
 
-    let workspace_root = project_root();
-    PipelineRuntime {
-        node_cmd: PathBuf::from("node"),
-        script_path: workspace_root.join("scripts/pipeline.js"),
-        use_tsx_loader: true,
-        working_dir: workspace_root,
+    let is_plain_name = Path::new(&expanded).file_name().map(|file_name| file_name == expanded.as_str()).unwrap_or(false);
+    if !is_plain_name {
+        return Err(format!("pattern must produce a plain file name, got: {}", expanded));
     }
+    Ok(expanded)
 }
 
-fn append_unique_path(paths: &mut Vec<PathBuf>, candidate: PathBuf) {
-    if candidate.as_os_str().is_empty() {
-        return;
-    }
+#[derive(Serialize)]
+struct RenamedFile {
+    from: String,
+    to: String,
+}
 
-    if !paths.iter().any(|existing| existing == &candidate) {
-        paths.push(candidate);
+fn plan_batch_rename(directory: &Path, pattern: &str, start_index: u32) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    let entries = fs::read_dir(directory).map_err(|error| format!("failed to read {}: {}", directory.display(), error))?;
+    let mut files = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| is_renameable_image(path))
+        .collect::<Vec<PathBuf>>();
+    files.sort_by(|left, right| natural_cmp(&left.to_string_lossy(), &right.to_string_lossy()));
+
+    let mut plan = Vec::with_capacity(files.len());
+    for (offset, source) in files.iter().enumerate() {
+        let index = u32::try_from(offset)
+            .ok()
+            .and_then(|offset| start_index.checked_add(offset))
+            .ok_or_else(|| "rename index overflowed".to_string())?;
+        let name = source.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+        plan.push((source.clone(), directory.join(expand_rename_pattern(pattern, index, &name)?)));
     }
-}
 
-fn extend_paths_from_string(paths: &mut Vec<PathBuf>, value: &str) {
-    for candidate in std::env::split_paths(value) {
-        append_unique_path(paths, candidate);
+    let mut targets = std::collections::HashSet::new();
+    for (_, target) in &plan {
+        if !targets.insert(target.clone()) {
+            return Err(format!("pattern maps several files to {}", target.display()));
+        }
+        if target.exists() && !files.contains(target) {
+            return Err(format!("refusing to overwrite existing file: {}", target.display()));
+        }
     }
+    Ok(plan)
 }
 
-fn resolve_login_shell_path() -> Option<String> {
-    #[cfg(unix)]
-    {
-        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string());
-        let output = Command::new(shell)
-            .args(["-lc", "printf %s \"$PATH\""])
-            .output()
-            .ok()?;
-
-        if !output.status.success() {
-            return None;
+#[tauri::command]
+fn batch_rename(app: tauri::AppHandle, dir: String, pattern: String, start_index: u32) -> Result<Vec<RenamedFile>, String> {
+    let directory = resolve_scoped_path(&app, dir.trim())?;
+    let plan = plan_batch_rename(&directory, &pattern, start_index)?;
+
+    // Stage every source under a temporary name first so swaps such as shot_1 -> shot_0 never
+    // overwrite a file that has not been moved yet.
+    let mut staged = Vec::with_capacity(plan.len());
+    for (source, target) in &plan {
+        let staging = (|| {
+            let temp_path = atomic_temp_path(source)?;
+            fs::rename(source, &temp_path).map_err(|error| format!("failed to rename {}: {}", source.display(), error))?;
+            Ok::<PathBuf, String>(temp_path)
+        })();
+        match staging {
+            Ok(temp_path) => staged.push((source, temp_path, target)),
+            Err(error) => {
+                for (source, temp_path, _) in staged.iter().rev() {
+                    let _ = fs::rename(temp_path, source);
+                }
+                return Err(error);
+            }
         }
+    }
 
-        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if value.is_empty() {
-            None
-        } else {
-            Some(value)
+    for (position, (source, temp_path, target)) in staged.iter().enumerate() {
+        if let Err(error) = fs::rename(temp_path, target) {
+            for (_, temp_path, target) in &staged[..position] {
+                let _ = fs::rename(target, temp_path);
+            }
+            for (source, temp_path, _) in &staged {
+                let _ = fs::rename(temp_path, source);
+            }
+            return Err(format!("failed to rename {} to {}: {}", source.display(), target.display(), error));
         }
     }
 
-    #[cfg(not(unix))]
-    {
-        None
-    }
+    let root = project_root();
+    Ok(plan
+        .iter()
+        .map(|(source, target)| RenamedFile {
+            from: to_display_path(source, &root),
+            to: to_display_path(target, &root),
+        })
+        .collect())
 }
 
-fn build_augmented_path_env() -> Option<String> {
-    let mut paths = Vec::new();
+#[derive(Serialize)]
+struct ClearExportSummary {
+    removed_count: usize,
+    total_bytes: u64,
+}
 
-    if let Ok(current_path) = std::env::var("PATH") {
-        extend_paths_from_string(&mut paths, &current_path);
+fn is_protected_directory(app: &tauri::AppHandle, directory: &Path) -> bool {
+    if directory.parent().is_none() || directory.components().count() < 3 {
+        return true;
     }
 
-    if let Some(login_shell_path) = resolve_login_shell_path() {
-        extend_paths_from_string(&mut paths, &login_shell_path);
+    if project_root().starts_with(directory) {
+        return true;
     }
 
-    #[cfg(target_os = "macos")]
-    {
-        for system_path in [
-            "/opt/homebrew/bin",
-            "/opt/homebrew/sbin",
-            "/usr/local/bin",
-            "/usr/local/sbin",
-            "/opt/local/bin",
-            "/opt/local/sbin",
-        ] {
-            append_unique_path(&mut paths, PathBuf::from(system_path));
-        }
+    let path = app.path();
+    [
+        path.home_dir(),
+        path.desktop_dir(),
+        path.document_dir(),
+        path.download_dir(),
+        path.picture_dir(),
+        path.app_data_dir(),
+        path.app_config_dir(),
+    ]
+    .into_iter()
+    .filter_map(Result::ok)
+    .filter_map(|protected| protected.canonicalize().ok())
+    .any(|protected| protected == directory)
+}
 
-        if let Ok(home) = std::env::var("HOME") {
-            let home = PathBuf::from(home);
-            for suffix in [
-                ".local/bin",
-                ".npm-global/bin",
-                ".volta/bin",
-                ".asdf/shims",
-                ".nvm/current/bin",
-            ] {
-                append_unique_path(&mut paths, home.join(suffix));
-            }
-        }
+#[tauri::command]
+async fn clear_export_dir(app: tauri::AppHandle) -> Result<ClearExportSummary, String> {
+    let configured = default_export_dir(&app)?;
+    let metadata = fs::symlink_metadata(&configured)
+        .map_err(|error| format!("failed to stat export dir {}: {}", configured.display(), error))?;
+    if !metadata.is_dir() {
+        return Err(format!("export dir is not a directory: {}", configured.display()));
+    }
+    let export_dir = configured
+        .canonicalize()
+        .map_err(|error| format!("failed to resolve export dir {}: {}", configured.display(), error))?;
+    if is_protected_directory(&app, &export_dir) {
+        return Err(format!("refusing to clear protected directory: {}", export_dir.display()));
     }
 
-    std::env::join_paths(paths)
-        .ok()
-        .map(|joined| joined.to_string_lossy().to_string())
+    tauri::async_runtime::spawn_blocking(move || {
+        let entries = fs::read_dir(&export_dir)
+            .map_err(|error| format!("failed to read dir {}: {}", export_dir.display(), error))?
+            .map(|entry| {
+                entry
+                    .map(|entry| entry.path())
+                    .map_err(|error| format!("read_dir entry failed: {}", error))
+            })
+            .collect::<Result<Vec<PathBuf>, String>>()?;
+
+        let total_bytes = entries
+            .iter()
+            .map(|entry| match fs::symlink_metadata(entry) {
+                Ok(metadata) if metadata.is_dir() => measure_directory(entry).total_bytes,
+                Ok(metadata) => metadata.len(),
+                Err(_) => 0,
+            })
+            .sum();
+        if !entries.is_empty() {
+            trash::delete_all(&entries).map_err(|error| format!("failed to move exports to trash: {}", error))?;
+        }
+
+        Ok(ClearExportSummary {
+            removed_count: entries.len(),
+            total_bytes,
+        })
+    })
+    .await
+    .map_err(|error| format!("failed to join clear_export_dir task: {}", error))?
 }
 
-fn resolve_pipeline_path_env() -> Option<String> {
-    static CACHED_PATH: OnceLock<Option<String>> = OnceLock::new();
-    CACHED_PATH.get_or_init(build_augmented_path_env).clone()
+#[derive(Serialize)]
+struct DiskSpace {
+    available_bytes: u64,
+    total_bytes: u64,
 }
 
 #[tauri::command]
-async fn run_pipeline(
-    app: tauri::AppHandle,
-    command: String,
-    args: Vec<String>,
-) -> Result<String, String> {
-    let runtime = resolve_pipeline_runtime(&app, &args);
-    tauri::async_runtime::spawn_blocking(move || {
-        let mut process = Command::new(&runtime.node_cmd);
-        if let Some(path_env) = resolve_pipeline_path_env() {
-            process.env("PATH", path_env);
-        }
+fn check_disk_space(app: tauri::AppHandle, path: String) -> Result<DiskSpace, String> {
+    let target = if path.trim().is_empty() {
+        default_export_dir(&app)?
+    } else {
+        resolve_scoped_path(&app, path.trim())?
+    };
+    let existing = target
+        .ancestors()
+        .find(|candidate| candidate.exists())
+        .ok_or_else(|| format!("no existing parent for {}", target.display()))?;
+
+    let available_bytes = fs2::available_space(existing)
+        .map_err(|error| format!("failed to read available space for {}: {}", existing.display(), error))?;
+    let total_bytes = fs2::total_space(existing)
+        .map_err(|error| format!("failed to read total space for {}: {}", existing.display(), error))?;
+    Ok(DiskSpace {
+        available_bytes,
+        total_bytes,
+    })
+}
 
-        if runtime.use_tsx_loader {
-            process.arg("--import").arg("tsx");
-        }
+#[derive(Serialize)]
+struct WriteProbe {
+    writable: bool,
+    reason: Option<String>,
+}
 
-        let output = process
-            .arg(&runtime.script_path)
-            .arg(command)
-            .args(args)
-            .current_dir(&runtime.working_dir)
-            .output()
-            .map_err(|error| {
-                format!(
-                    "failed to execute node `{}` with script `{}`: {}",
-                    runtime.node_cmd.display(),
-                    runtime.script_path.display(),
-                    error
-                )
-            })?;
-
-        if !output.status.success() {
-            return Err(String::from_utf8_lossy(&output.stderr).to_string());
-        }
+fn probe_directory_write(directory: &Path) -> Result<(), String> {
+    if !directory.is_dir() {
+        return Err(format!("not a directory: {}", directory.display()));
+    }
 
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
-    })
-    .await
-    .map_err(|error| format!("failed to join run_pipeline task: {}", error))?
+    let probe = atomic_temp_path(&directory.join(".dma-write-test"))?;
+    let written = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .and_then(|mut file| file.write_all(b"ok").and_then(|_| file.sync_all()));
+    let removed = fs::remove_file(&probe);
+    written.map_err(|error| format!("failed to write to {}: {}", directory.display(), error))?;
+    removed.map_err(|error| format!("failed to remove probe file in {}: {}", directory.display(), error))
 }
 
 #[tauri::command]
-fn read_text_file(path: String) -> Result<String, String> {
-    let resolved = resolve_project_path(&path);
-    fs::read_to_string(&resolved).map_err(|error| format!("failed to read {}: {}", resolved.display(), error))
+async fn can_write_dir(app: tauri::AppHandle, path: String) -> Result<WriteProbe, String> {
+    let directory = resolve_scoped_path(&app, path.trim())?;
+    // Disconnected network shares can block for a long time, so keep the probe off the main thread.
+    tauri::async_runtime::spawn_blocking(move || match probe_directory_write(&directory) {
+        Ok(()) => WriteProbe {
+            writable: true,
+            reason: None,
+        },
+        Err(reason) => WriteProbe {
+            writable: false,
+            reason: Some(reason),
+        },
+    })
+    .await
+    .map_err(|error| format!("failed to join can_write_dir task: {}", error))
 }
 
-#[tauri::command]
-fn write_text_file(path: String, content: String) -> Result<(), String> {
-    let resolved = resolve_project_path(&path);
+fn open_with_system(target: &OsStr) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = std::process::Command::new("explorer");
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = std::process::Command::new("xdg-open");
+
+    command
+        .arg(target)
+        .spawn()
+        .map(|_| ())
+        .map_err(|error| format!("failed to open {}: {}", target.to_string_lossy(), error))
+}
 
-    if let Some(parent) = resolved.parent() {
-        fs::create_dir_all(parent).map_err(|error| format!("failed to create parent dirs: {}", error))?;
+fn parse_external_url(raw: &str) -> Result<tauri::Url, String> {
+    let url = tauri::Url::parse(raw.trim()).map_err(|error| format!("invalid url: {}", error))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("refusing to open {} url", url.scheme()));
     }
-
-    fs::write(&resolved, content).map_err(|error| format!("failed to write {}: {}", resolved.display(), error))
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(format!("url has no host: {}", url));
+    }
+    Ok(url)
 }
 
 #[tauri::command]
-fn list_png_files(path: String) -> Result<Vec<String>, String> {
-    let resolved = resolve_project_path(&path);
-    if !resolved.exists() {
-        return Ok(Vec::new());
-    }
+fn open_external_url(url: String) -> Result<(), String> {
+    let url = parse_external_url(&url)?;
+    open_with_system(OsStr::new(url.as_str()))
+}
 
-    let mut files = Vec::new();
-    collect_png_files(&resolved, &mut files)?;
-    files.sort();
+#[derive(Serialize)]
+struct AppInfo {
+    version: &'static str,
+    tauri_version: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    commit: &'static str,
+}
 
-    let root = project_root();
-    let results = files
-        .into_iter()
-        .map(|file| {
-            file.strip_prefix(&root)
-                .map(|relative| relative.to_string_lossy().replace('\\', "/"))
-                .unwrap_or_else(|_| file.to_string_lossy().replace('\\', "/"))
-        })
-        .collect::<Vec<String>>();
+#[tauri::command]
+fn get_app_info() -> AppInfo {
+    AppInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        tauri_version: tauri::VERSION,
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        commit: env!("DMA_GIT_COMMIT"),
+    }
+}
 
-    Ok(results)
+// Config stores write synchronously; only the debounced window-state save can still be pending.
+fn flush_pending_saves(app: &tauri::AppHandle) {
+    window_state::flush_window_state(app);
 }
 
 #[tauri::command]
-fn read_file_base64(path: String) -> Result<String, String> {
-    let resolved = resolve_project_path(&path);
-    let bytes = fs::read(&resolved).map_err(|error| format!("failed to read {}: {}", resolved.display(), error))?;
-    Ok(STANDARD.encode(bytes))
+fn relaunch_app(app: tauri::AppHandle) {
+    flush_pending_saves(&app);
+    app.request_restart();
 }
 
 #[tauri::command]
-fn write_file_base64(path: String, data_base64: String) -> Result<(), String> {
-    let resolved = resolve_project_path(&path);
-
-    if let Some(parent) = resolved.parent() {
-        fs::create_dir_all(parent).map_err(|error| format!("failed to create parent dirs: {}", error))?;
-    }
+fn exit_app(app: tauri::AppHandle, code: Option<i32>) {
+    flush_pending_saves(&app);
+    app.exit(code.unwrap_or(0));
+}
 
-    let bytes = STANDARD
-        .decode(data_base64.as_bytes())
-        .map_err(|error| format!("failed to decode base64: {}", error))?;
+fn window_by_label(app: &tauri::AppHandle, label: &str) -> Result<tauri::WebviewWindow, String> {
+    let label = label.trim();
+    app.get_webview_window(label).ok_or_else(|| format!("no window with label: {}", label))
+}
 
-    fs::write(&resolved, bytes).map_err(|error| format!("failed to write {}: {}", resolved.display(), error))?;
-    Ok(())
+#[tauri::command]
+fn set_window_title(app: tauri::AppHandle, label: String, title: String) -> Result<(), String> {
+    window_by_label(&app, &label)?
+        .set_title(&title)
+        .map_err(|error| format!("failed to set window title: {}", error))
 }
 
 #[tauri::command]
-fn get_default_export_dir(app: tauri::AppHandle) -> Option<String> {
-    let home_dir = app.path().home_dir().ok()?;
-    let default_dir = home_dir.join("Don't Mockup Again");
+fn get_window_title(app: tauri::AppHandle, label: String) -> Result<String, String> {
+    window_by_label(&app, &label)?.title().map_err(|error| format!("failed to read window title: {}", error))
+}
+
+#[derive(Default)]
+struct LastDialogDirectory(Mutex<Option<PathBuf>>);
+
+impl LastDialogDirectory {
+    fn start_directory(&self, preferred_dir: Option<String>) -> Option<PathBuf> {
+        resolve_dialog_directory(preferred_dir).or_else(|| {
+            self.0
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone()
+                .filter(|directory| directory.is_dir())
+        })
+    }
 
-    fs::create_dir_all(&default_dir).ok()?;
-    Some(default_dir.to_string_lossy().replace('\\', "/"))
+    fn remember(&self, directory: Option<&Path>) {
+        if let Some(directory) = directory {
+            *self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(directory.to_path_buf());
+        }
+    }
 }
 
 #[tauri::command]
-fn pick_output_dir() -> Option<String> {
-    rfd::FileDialog::new()
-        .pick_folder()
-        .map(|path| path.to_string_lossy().replace('\\', "/"))
+fn pick_output_dir(app: tauri::AppHandle, last_dir: tauri::State<'_, LastDialogDirectory>, preferred_dir: Option<String>) -> Option<String> {
+    let mut dialog = rfd::FileDialog::new();
+    if let Some(directory) = last_dir.start_directory(preferred_dir) {
+        dialog = dialog.set_directory(directory);
+    }
+
+    let picked = dialog.pick_folder()?;
+    last_dir.remember(Some(&picked));
+    path_scope::allow_path(&app, &picked, true);
+    Some(picked.to_string_lossy().replace('\\', "/"))
 }
 
 #[tauri::command]
-fn pick_project_file(preferred_dir: Option<String>) -> Option<String> {
+fn pick_project_file(app: tauri::AppHandle, last_dir: tauri::State<'_, LastDialogDirectory>, preferred_dir: Option<String>) -> Option<String> {
     let mut dialog = rfd::FileDialog::new().add_filter("Don't Mockup Again Project", &["json"]);
-    if let Some(directory) = resolve_dialog_directory(preferred_dir) {
+    if let Some(directory) = last_dir.start_directory(preferred_dir) {
         dialog = dialog.set_directory(directory);
     }
-    dialog.pick_file().map(|path| path.to_string_lossy().replace('\\', "/"))
+
+    let picked = dialog.pick_file()?;
+    last_dir.remember(picked.parent());
+    if let Some(parent) = picked.parent() {
+        path_scope::allow_path(&app, parent, true);
+    }
+    Some(picked.to_string_lossy().replace('\\', "/"))
 }
 
 #[tauri::command]
-fn pick_project_save_path(default_file_name: Option<String>, preferred_dir: Option<String>) -> Option<String> {
+fn pick_project_save_path(
+    app: tauri::AppHandle,
+    last_dir: tauri::State<'_, LastDialogDirectory>,
+    default_file_name: Option<String>,
+    preferred_dir: Option<String>,
+) -> Option<String> {
     let file_name = default_file_name
         .map(|value| value.trim().to_string())
         .filter(|value| !value.is_empty())
@@ -510,45 +1815,534 @@ fn pick_project_save_path(default_file_name: Option<String>, preferred_dir: Opti
     let mut dialog = rfd::FileDialog::new()
         .add_filter("Don't Mockup Again Project", &["json"])
         .set_file_name(&file_name);
-    if let Some(directory) = resolve_dialog_directory(preferred_dir) {
+    if let Some(directory) = last_dir.start_directory(preferred_dir) {
         dialog = dialog.set_directory(directory);
     }
 
-    dialog.save_file().map(|path| path.to_string_lossy().replace('\\', "/"))
+    let picked = dialog.save_file()?;
+    last_dir.remember(picked.parent());
+    if let Some(parent) = picked.parent() {
+        path_scope::allow_path(&app, parent, true);
+    }
+    Some(picked.to_string_lossy().replace('\\', "/"))
 }
 
 #[tauri::command]
-fn list_system_fonts() -> Result<Vec<String>, String> {
-    Ok(collect_system_fonts().unwrap_or_else(|_| fallback_font_list()))
+fn pick_image_save_path(
+    app: tauri::AppHandle,
+    last_dir: tauri::State<'_, LastDialogDirectory>,
+    default_file_name: Option<String>,
+    preferred_dir: Option<String>,
+    format: Option<String>,
+) -> Result<Option<String>, String> {
+    let (filter_name, extensions): (&str, &[&str]) = match format.as_deref().map(str::trim).unwrap_or("png") {
+        "png" => ("PNG Image", &["png"]),
+        "jpeg" | "jpg" => ("JPEG Image", &["jpg", "jpeg"]),
+        "webp" => ("WebP Image", &["webp"]),
+        other => return Err(format!("unsupported image format: {}", other)),
+    };
+
+    let file_name = default_file_name
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "mockup".to_string());
+    let file_name = match Path::new(&file_name).extension().and_then(|value| value.to_str()) {
+        Some(extension) if extensions.iter().any(|accepted| extension.eq_ignore_ascii_case(accepted)) => file_name,
+        _ => format!("{}.{}", Path::new(&file_name).with_extension("").to_string_lossy(), extensions[0]),
+    };
+
+    let mut dialog = rfd::FileDialog::new()
+        .add_filter(filter_name, extensions)
+        .set_file_name(&file_name);
+    if let Some(directory) = last_dir.start_directory(preferred_dir) {
+        dialog = dialog.set_directory(directory);
+    }
+
+    let Some(mut picked) = dialog.save_file() else {
+        return Ok(None);
+    };
+    if picked.extension().is_none() {
+        picked.set_extension(extensions[0]);
+    }
+    last_dir.remember(picked.parent());
+    if let Some(parent) = picked.parent() {
+        path_scope::allow_path(&app, parent, false);
+    }
+    Ok(Some(picked.to_string_lossy().replace('\\', "/")))
+}
+
+fn handle_window_event<R: tauri::Runtime>(window: &tauri::Window<R>, event: &tauri::WindowEvent) {
+    window_state::handle_window_event(window, event);
+    drag_drop::handle_window_event(window, event);
+    theme::handle_window_event(window, event);
+    temp_dirs::handle_window_event(window, event);
 }
 
 fn main() {
     tauri::Builder::default()
+        .plugin(logging::plugin())
+        .register_asynchronous_uri_scheme_protocol(asset_protocol::ASSET_SCHEME, asset_protocol::handle_asset_request)
+        .manage(window_state::WindowStateTracker::default())
+        .manage(LastDialogDirectory::default())
+        .manage(path_scope::PathScope::default())
+        .manage(temp_dirs::TempDirRegistry::default())
+        .manage(tail::TailRegistry::default())
+        .manage(dir_watch::DirWatchRegistry::default())
+        .manage(pipeline::PipelineLimiter::default())
+        .manage(chunked_write::ChunkedWrites::default())
+        .manage(chunked_read::ChunkedReads::default())
+        .manage(scan::ScanRegistry::default())
+        .manage(zoom::ZoomState::default())
+        .manage(project_watch::ProjectWatch::default())
+        .manage(clipboard::ClipboardState::default())
+        .manage(drag_drop::DragHoverState::default())
+        .manage(fonts::FontCache::default())
+        .manage(fonts::FontWatcher::default())
         .setup(|app| {
             #[cfg(target_os = "macos")]
             for (_, webview_window) in app.webview_windows() {
                 disable_swipe_navigation(&webview_window);
             }
 
+            path_scope::restore_path_scope(app.handle());
+            window_state::restore_window_state(app.handle());
+            zoom::restore_zoom(app.handle());
+            fonts::start_font_watcher(app.handle());
+
             Ok(())
         })
-        .on_page_load(|window, _payload| {
+        .on_page_load(|webview, _payload| {
             #[cfg(target_os = "macos")]
-            disable_swipe_navigation_in_webview(window);
+            disable_swipe_navigation_in_webview(webview);
+            #[cfg(not(target_os = "macos"))]
+            let _ = webview;
         })
+        .on_window_event(handle_window_event)
         .invoke_handler(tauri::generate_handler![
-            run_pipeline,
+            pipeline::run_pipeline,
+            pipeline::start_pipeline,
+            pipeline::enqueue_pipeline_jobs,
+            pipeline::list_pipeline_jobs,
+            pipeline::drain_pipeline_jobs,
+            pipeline::check_runtime,
+            pipeline::check_pipeline_script,
+            pipeline::check_pipeline_runtime,
+            pipeline::validate_pipeline,
+            pipeline::cancel_pipeline,
+            pipeline::set_pipeline_concurrency,
+            pipeline_settings::get_pipeline_settings,
+            pipeline_settings::set_pipeline_settings,
+            pipeline_settings::detect_node_installs,
+            get_app_info,
+            open_external_url,
+            relaunch_app,
+            exit_app,
+            set_window_title,
+            get_window_title,
             read_text_file,
             write_text_file,
+            read_json,
+            write_json,
             list_png_files,
+            list_image_files,
+            glob_files,
+            scan::scan_png_files,
+            scan::cancel_scan,
+            dir_watch::watch_directory,
+            dir_watch::unwatch_directory,
+            screen_permission::check_screen_permission,
+            screen_permission::request_screen_permission,
             read_file_base64,
+            read_files_base64,
+            read_file_range,
+            chunked_read::open_file_stream,
+            chunked_read::read_chunk,
+            chunked_read::close_stream,
+            detect_mime,
             write_file_base64,
+            chunked_write::begin_file_write,
+            chunked_write::append_file_chunk,
+            chunked_write::finish_file_write,
+            chunked_write::abort_file_write,
+            delete_paths,
+            delete_path,
+            move_path,
+            prune_empty_dirs,
+            directory_size,
+            stat_file,
+            export_file,
+            import_asset,
+            copy_files,
+            batch_rename,
+            clear_export_dir,
+            to_project_relative,
+            to_absolute,
+            is_within_project,
             get_default_export_dir,
+            set_default_export_dir,
+            check_disk_space,
+            can_write_dir,
+            path_scope::list_allowed_roots,
+            path_scope::revoke_allowed_root,
             pick_output_dir,
             pick_project_file,
             pick_project_save_path,
-            list_system_fonts
+            pick_image_save_path,
+            fonts::list_system_fonts,
+            fonts::list_system_fonts_detailed,
+            fonts::record_font_use,
+            fonts::list_recent_fonts,
+            fonts::render_font_preview,
+            fonts::resolve_font_fallback,
+            fonts::set_font_autorefresh,
+            fonts::fonts_covering_text,
+            fonts::read_font_info,
+            clipboard::read_clipboard_image,
+            clipboard::write_clipboard_image,
+            clipboard::get_clipboard_text,
+            clipboard::set_clipboard_text,
+            recent_projects::add_recent_project,
+            recent_projects::list_recent_projects,
+            project_validation::validate_project_file,
+            presets::list_presets,
+            presets::load_preset,
+            project_watch::begin_watch_project,
+            project_watch::stop_watch_project,
+            images::get_image_dimensions,
+            images::optimize_png,
+            images::optimize_pngs,
+            images::crop_image,
+            images::rasterize_svg,
+            images::generate_contact_sheet,
+            thumbnails::generate_thumbnail,
+            thumbnails::clear_thumbnail_cache,
+            thumbnails::thumbnail_cache_dir,
+            images::flatten_image,
+            images::read_image_metadata,
+            images::read_color_profile,
+            images::auto_orient_image,
+            images::diff_images,
+            images::perceptual_hash,
+            images::group_similar_images,
+            images::save_image_base64,
+            images::composite_onto_frame,
+            render::render_native,
+            archive::export_zip,
+            bundle::export_bundle,
+            bundle::import_bundle,
+            bundle::export_project_archive,
+            bundle::import_project_archive,
+            logging::get_log_path,
+            logging::reveal_logs,
+            temp_dirs::create_temp_dir,
+            temp_dirs::cleanup_temp_dir,
+            snapshots::snapshot_project,
+            snapshots::list_snapshots,
+            snapshots::restore_snapshot,
+            project_backups::list_project_backups,
+            project_backups::restore_project_backup,
+            tail::tail_file,
+            tail::stop_tail,
+            theme::get_system_theme,
+            display::get_display_info,
+            zoom::set_zoom,
+            zoom::get_zoom,
+            preview_window::open_preview_window,
+            preview_window::close_preview_window
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dma-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn resolve_with_root_keeps_absolute_inputs() {
+        let absolute = std::env::temp_dir().join("elsewhere").join("shot.png");
+        let resolved = resolve_with_root(Path::new("workspace-root"), &absolute.to_string_lossy());
+        assert_eq!(resolved, absolute);
+    }
+
+    #[test]
+    fn resolve_with_root_joins_nested_relatives() {
+        let root = std::env::temp_dir().join("workspace-root");
+        let resolved = resolve_with_root(&root, "assets/source/shot1.png");
+        assert_eq!(resolved, root.join("assets").join("source").join("shot1.png"));
+    }
+
+    #[test]
+    fn resolve_dialog_directory_ignores_empty_input() {
+        let root = scratch_dir("dialog-empty");
+        assert_eq!(resolve_dialog_directory_with_root(&root, None), None);
+        assert_eq!(resolve_dialog_directory_with_root(&root, Some(String::new())), None);
+        assert_eq!(resolve_dialog_directory_with_root(&root, Some("   ".to_string())), None);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn resolve_dialog_directory_uses_parent_of_files_under_root() {
+        let root = scratch_dir("dialog-nested");
+        fs::create_dir_all(root.join("projects/demo")).unwrap();
+        fs::write(root.join("projects/demo/project.dma.json"), "{}").unwrap();
+
+        let from_file =
+            resolve_dialog_directory_with_root(&root, Some("projects/demo/project.dma.json".to_string()));
+        assert_eq!(from_file, Some(root.join("projects/demo")));
+
+        let from_dir = resolve_dialog_directory_with_root(&root, Some(" projects ".to_string()));
+        assert_eq!(from_dir, Some(root.join("projects")));
+
+        assert_eq!(resolve_dialog_directory_with_root(&root, Some("missing/dir".to_string())), None);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn normalize_line_endings_unifies_mixed_newlines() {
+        let mixed = "a\r\nb\nc\rd";
+        assert_eq!(normalize_line_endings(mixed, LineEnding::Lf), "a\nb\nc\nd");
+        assert_eq!(normalize_line_endings(mixed, LineEnding::Crlf), "a\r\nb\r\nc\r\nd");
+    }
+
+    #[test]
+    fn serialize_json_uses_two_space_indent_when_pretty() {
+        let value = serde_json::json!({ "name": "demo", "tags": ["a"] });
+        assert_eq!(serialize_json(&value, true).unwrap(), "{\n  \"name\": \"demo\",\n  \"tags\": [\n    \"a\"\n  ]\n}\n");
+        assert_eq!(serialize_json(&value, false).unwrap(), "{\"name\":\"demo\",\"tags\":[\"a\"]}");
+    }
+
+    #[test]
+    fn numbered_file_name_inserts_counter_before_extension() {
+        assert_eq!(numbered_file_name(OsStr::new("shot.png"), 1), OsString::from("shot.png"));
+        assert_eq!(numbered_file_name(OsStr::new("shot.png"), 2), OsString::from("shot (2).png"));
+        assert_eq!(numbered_file_name(OsStr::new("archive.tar.gz"), 3), OsString::from("archive.tar (3).gz"));
+        assert_eq!(numbered_file_name(OsStr::new("README"), 2), OsString::from("README (2)"));
+    }
+
+    #[test]
+    fn parse_external_url_only_allows_web_schemes() {
+        assert_eq!(parse_external_url(" https://example.com/help ").unwrap().as_str(), "https://example.com/help");
+        assert!(parse_external_url("http://example.com").is_ok());
+        assert!(parse_external_url("file:///etc/passwd").is_err());
+        assert!(parse_external_url("javascript:alert(1)").is_err());
+        assert!(parse_external_url("not a url").is_err());
+    }
+
+    #[test]
+    fn expand_rename_pattern_substitutes_placeholders() {
+        assert_eq!(expand_rename_pattern("demo-{index:03}.png", 7, "shot_0").unwrap(), "demo-007.png");
+        assert_eq!(expand_rename_pattern("{name}-{index}.png", 12, "shot_0").unwrap(), "shot_0-12.png");
+        assert!(expand_rename_pattern("{project}.png", 1, "shot").is_err());
+        assert!(expand_rename_pattern("{index.png", 1, "shot").is_err());
+        assert!(expand_rename_pattern("../{index}.png", 1, "shot").is_err());
+    }
+
+    #[test]
+    fn natural_cmp_orders_numbers_by_value() {
+        let mut names = vec!["shot_10.png", "shot_2.png", "shot_1.png", "shot_02.png"];
+        names.sort_by(|left, right| natural_cmp(left, right));
+        assert_eq!(names, vec!["shot_1.png", "shot_02.png", "shot_2.png", "shot_10.png"]);
+    }
+
+    #[test]
+    fn batch_rename_plan_refuses_collisions_with_other_files() {
+        let root = scratch_dir("rename");
+        fs::write(root.join("shot_0.png"), b"a").unwrap();
+        fs::write(root.join("shot_1.png"), b"b").unwrap();
+        fs::write(root.join("final-0.txt"), b"c").unwrap();
+
+        let plan = plan_batch_rename(&root, "shot_{index}.png", 1).unwrap();
+        assert_eq!(plan[0], (root.join("shot_0.png"), root.join("shot_1.png")));
+        assert_eq!(plan[1], (root.join("shot_1.png"), root.join("shot_2.png")));
+        assert!(plan_batch_rename(&root, "same.png", 0).is_err());
+        assert!(plan_batch_rename(&root, "final-{index}.txt", 0).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn ensure_within_roots_rejects_traversal_and_the_root_itself() {
+        let root = scratch_dir("guard");
+        fs::create_dir_all(root.join("exports")).unwrap();
+        fs::write(root.join("exports/shot.png"), b"png").unwrap();
+        let roots = vec![root.join("exports")];
+
+        assert!(ensure_within_roots(&root.join("exports/shot.png"), &roots).is_ok());
+        assert!(ensure_within_roots(&root.join("exports/../exports/shot.png"), &roots).is_ok());
+        assert!(ensure_within_roots(&root.join("exports/../outside.png"), &roots).is_err());
+        assert!(ensure_within_roots(&root.join("exports"), &roots).is_err());
+        assert!(ensure_within_roots(&root.join("exports/.."), &roots).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn contained_path_handles_missing_paths_without_allowing_escapes() {
+        let root = scratch_dir("contained");
+        fs::create_dir_all(root.join("projects")).unwrap();
+        let roots = vec![root.clone()];
+
+        assert!(contained_path(&root.join("projects/new/project.dma.json"), &roots).is_some());
+        assert!(contained_path(&root, &roots).is_some());
+        assert!(contained_path(&root.join("missing/../../escape.json"), &roots).is_none());
+        assert!(contained_path(&root.join("../escape.json"), &roots).is_none());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn png_scan_skips_ignored_dirs_and_honours_depth_and_excludes() {
+        let dir = scratch_dir("scan-rules");
+        for nested in ["a/b", ".cache", "node_modules/pkg", "dist"] {
+            fs::create_dir_all(dir.join(nested)).unwrap();
+        }
+        for file in ["top.png", "a/one.png", "a/b/two.png", ".cache/hidden.png", "node_modules/pkg/icon.png", "dist/out.png", "a/draft.png"] {
+            fs::write(dir.join(file), b"png-bytes").unwrap();
+        }
+        let scan = |rules: ScanRules| {
+            let mut files = collect_files_matching(&dir, &rules, &is_png_file)
+                .unwrap()
+                .into_iter()
+                .map(|file| file.strip_prefix(&dir).unwrap().to_string_lossy().replace('\\', "/"))
+                .collect::<Vec<String>>();
+            files.sort();
+            files
+        };
+
+        assert_eq!(scan(ScanRules::default()), vec!["a/b/two.png", "a/draft.png", "a/one.png", "dist/out.png", "top.png"]);
+        assert_eq!(scan(ScanRules::new(Some(0), &[]).unwrap()), vec!["top.png"]);
+        assert_eq!(
+            scan(ScanRules::new(Some(1), &["dist".to_string(), "draft.png".to_string()]).unwrap()),
+            vec!["a/one.png", "top.png"]
+        );
+        assert!(ScanRules::new(None, &["[".to_string()]).is_err());
+        assert_eq!(
+            scan(ScanRules::unfiltered()),
+            vec![".cache/hidden.png", "a/b/two.png", "a/draft.png", "a/one.png", "dist/out.png", "node_modules/pkg/icon.png", "top.png"]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn listing_ids_round_trip_non_utf8_file_names() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = scratch_dir("listing");
+        let file_name = OsStr::from_bytes(b"shot-\xff\xfe.png");
+        let file_path = dir.join(file_name);
+        fs::write(&file_path, b"png-bytes").unwrap();
+
+        let files = collect_png_files(&dir).unwrap();
+        assert_eq!(files, vec![file_path.clone()]);
+
+        let display = to_display_path(&files[0], Path::new("/nonexistent-root"));
+        assert!(display.ends_with("shot-\u{fffd}\u{fffd}.png"));
+        assert!(fs::read(resolve_project_path(&display)).is_err());
+
+        let resolved = resolve_file_id(&encode_file_id(&files[0])).unwrap();
+        assert_eq!(resolved, file_path);
+        assert_eq!(fs::read(&resolved).unwrap(), b"png-bytes");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn image_format_matches_extensions_case_insensitively() {
+        let extensions = DEFAULT_IMAGE_EXTENSIONS
+            .iter()
+            .map(|extension| extension.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            image_format(Path::new("shots/Home.PNG"), &extensions).as_deref(),
+            Some("png")
+        );
+        assert_eq!(
+            image_format(Path::new("shots/home.JPG"), &extensions).as_deref(),
+            Some("jpeg")
+        );
+        assert_eq!(
+            image_format(Path::new("shots/home.WebP"), &extensions).as_deref(),
+            Some("webp")
+        );
+        assert_eq!(image_format(Path::new("shots/home.gif"), &extensions), None);
+        assert_eq!(image_format(Path::new("shots/png"), &extensions), None);
+    }
+
+    #[test]
+    fn backup_existing_file_keeps_previous_contents() {
+        let dir = scratch_dir("backup");
+        let target = dir.join("project.storeshot.json");
+        backup_existing_file(&target).unwrap();
+        assert!(!backup_path(&target).exists());
+
+        fs::write(&target, b"old").unwrap();
+        backup_existing_file(&target).unwrap();
+        write_atomically(&target, b"new").unwrap();
+        assert_eq!(fs::read(&target).unwrap(), b"new");
+        assert_eq!(fs::read(dir.join("project.storeshot.json.bak")).unwrap(), b"old");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn split_glob_pattern_separates_literal_prefix() {
+        assert_eq!(
+            split_glob_pattern("screenshots/**/iphone-*-{en,de}.png"),
+            ("screenshots".to_string(), "**/iphone-*-{en,de}.png".to_string())
+        );
+        assert_eq!(split_glob_pattern("*.png"), (String::new(), "*.png".to_string()));
+        assert_eq!(
+            split_glob_pattern("/exports/ios/shot.png"),
+            ("/exports/ios/shot.png".to_string(), String::new())
+        );
+    }
+
+    #[test]
+    fn decode_text_strips_byte_order_marks() {
+        assert_eq!(decode_text(b"\xEF\xBB\xBF{\"a\":1}", None).unwrap(), "{\"a\":1}");
+        assert_eq!(decode_text(b"\xFF\xFE{\x00}\x00", None).unwrap(), "{}");
+        assert_eq!(decode_text(b"\xFE\xFF\x00{\x00}", None).unwrap(), "{}");
+    }
+
+    #[test]
+    fn decode_text_prefers_bom_over_override() {
+        let decoded = decode_text(b"\xEF\xBB\xBF{}", Some(TextEncoding::Utf16Le)).unwrap();
+        assert_eq!(decoded, "{}");
+    }
+
+    #[test]
+    fn decode_text_applies_override_without_bom() {
+        assert_eq!(decode_text(b"{\x00}\x00", Some(TextEncoding::Utf16Le)).unwrap(), "{}");
+        assert_eq!(decode_text(b"\x00{\x00}", Some(TextEncoding::Utf16Be)).unwrap(), "{}");
+        assert_eq!(decode_text(b"{}", None).unwrap(), "{}");
+        assert!(decode_text(b"\xFF\xFE{", None).is_err());
+        assert!(TextEncoding::parse("latin1").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn scoped_target_follows_symlinked_leaves() {
+        let dir = scratch_dir("scoped-target");
+        fs::create_dir_all(dir.join("root")).unwrap();
+        fs::write(dir.join("root/inside.txt"), b"inside").unwrap();
+        fs::write(dir.join("secret.txt"), b"secret").unwrap();
+        std::os::unix::fs::symlink(dir.join("secret.txt"), dir.join("root/escape.txt")).unwrap();
+        std::os::unix::fs::symlink(dir.join("root/inside.txt"), dir.join("root/alias.txt")).unwrap();
+        std::os::unix::fs::symlink(dir.join("missing.txt"), dir.join("root/dangling.txt")).unwrap();
+        let roots = vec![dir.join("root")];
+
+        assert!(scoped_target(&dir.join("root/inside.txt"), &roots).is_some());
+        assert!(scoped_target(&dir.join("root/alias.txt"), &roots).is_some());
+        assert!(scoped_target(&dir.join("root/new.txt"), &roots).is_some());
+        assert!(scoped_target(&dir.join("root/escape.txt"), &roots).is_none());
+        assert!(scoped_target(&dir.join("root/dangling.txt"), &roots).is_none());
+        assert!(contained_path(&dir.join("root/escape.txt"), &roots).is_some());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::Manager;
+
+use crate::{config_store, recent_projects};
+
+const PATH_SCOPE_FILE: &str = "path-scope.json";
+const MAX_PERSISTED_ROOTS: usize = 50;
+
+struct ScopedRoot {
+    path: PathBuf,
+    persisted: bool,
+}
+
+// Roots granted by the user through native dialogs (persisted) or drag and drop (this session
+// only). The project root and export dir are always allowed and are not stored here.
+#[derive(Default)]
+pub struct PathScope(Mutex<Vec<ScopedRoot>>);
+
+#[derive(Default, Serialize, Deserialize)]
+struct StoredPathScope {
+    roots: Vec<String>,
+}
+
+fn save_persisted<R: tauri::Runtime>(app: &tauri::AppHandle<R>, roots: &[ScopedRoot]) {
+    let stored = StoredPathScope {
+        roots: roots
+            .iter()
+            .filter(|root| root.persisted)
+            .map(|root| root.path.to_string_lossy().to_string())
+            .collect(),
+    };
+    if let Err(error) = config_store::save_json(app, PATH_SCOPE_FILE, &stored) {
+        log::warn!("failed to persist path scope: {}", error);
+    }
+}
+
+pub fn restore_path_scope(app: &tauri::AppHandle) {
+    let stored = config_store::load_json::<_, StoredPathScope>(app, PATH_SCOPE_FILE);
+    let migrating = stored.is_none();
+    // Installs from before the scope only know their projects through the recent list, so
+    // those folders are granted once instead of failing as outside the allowed roots.
+    let paths = match stored {
+        Some(stored) => stored.roots.into_iter().map(PathBuf::from).collect(),
+        None => recent_projects::recent_project_dirs(app),
+    };
+
+    let scope = app.state::<PathScope>();
+    let mut roots = scope
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    for path in paths {
+        if !roots.iter().any(|root| root.path == path) {
+            roots.push(ScopedRoot {
+                path,
+                persisted: true,
+            });
+        }
+    }
+    if migrating {
+        save_persisted(app, &roots);
+    }
+}
+
+pub(crate) fn allow_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>, path: &Path, persist: bool) {
+    let Ok(canonical) = path.canonicalize() else {
+        return;
+    };
+    let scope = app.state::<PathScope>();
+    let mut roots = scope
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let existing = roots.iter().position(|root| root.path == canonical);
+    let persisted = persist || existing.is_some_and(|index| roots[index].persisted);
+    if let Some(index) = existing {
+        roots.remove(index);
+    }
+    roots.push(ScopedRoot {
+        path: canonical,
+        persisted,
+    });
+
+    // Folders of recent projects are evicted last so the recent list keeps opening.
+    let recent_dirs = recent_projects::recent_project_dirs(app);
+    while roots.iter().filter(|root| root.persisted).count() > MAX_PERSISTED_ROOTS {
+        let oldest = roots
+            .iter()
+            .position(|root| root.persisted && !recent_dirs.contains(&root.path))
+            .or_else(|| roots.iter().position(|root| root.persisted));
+        if let Some(oldest) = oldest {
+            roots.remove(oldest);
+        }
+    }
+    if persisted {
+        save_persisted(app, &roots);
+    }
+}
+
+pub(crate) fn granted_roots(app: &tauri::AppHandle) -> Vec<PathBuf> {
+    app.state::<PathScope>()
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter()
+        .map(|root| root.path.clone())
+        .collect()
+}
+
+#[tauri::command]
+pub fn list_allowed_roots(app: tauri::AppHandle) -> Vec<String> {
+    crate::allowed_roots(&app)
+        .iter()
+        .map(|root| root.to_string_lossy().replace('\\', "/"))
+        .collect()
+}
+
+#[tauri::command]
+pub fn revoke_allowed_root(app: tauri::AppHandle, path: String) -> bool {
+    let resolved = crate::resolve_project_path(path.trim());
+    let canonical = resolved.canonicalize().unwrap_or(resolved);
+    let scope = app.state::<PathScope>();
+    let mut roots = scope
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let Some(index) = roots.iter().position(|root| root.path == canonical) else {
+        return false;
+    };
+    if roots.remove(index).persisted {
+        save_persisted(&app, &roots);
+    }
+    true
+}
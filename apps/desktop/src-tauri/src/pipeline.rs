@@ -0,0 +1,1478 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+use crate::project_root;
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RuntimeSource {
+    Configured,
+    Sidecar,
+    System,
+}
+
+struct PipelineRuntime {
+    source: RuntimeSource,
+    node_cmd: PathBuf,
+    script_path: PathBuf,
+    use_tsx_loader: bool,
+    working_dir: PathBuf,
+    env: BTreeMap<String, String>,
+}
+
+fn resolve_pipeline_working_dir(args: &[String]) -> PathBuf {
+    if let Some(first) = args.first() {
+        let candidate = PathBuf::from(first);
+        if candidate.is_absolute() {
+            if candidate.is_dir() {
+                return candidate;
+            }
+            if let Some(parent) = candidate.parent() {
+                return parent.to_path_buf();
+            }
+        }
+    }
+
+    std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/"))
+}
+
+// Settings saved via set_pipeline_settings override the detected node binary and working dir.
+fn resolve_pipeline_runtime(app: &tauri::AppHandle, args: &[String]) -> PipelineRuntime {
+    let settings = crate::pipeline_settings::load_pipeline_settings(app);
+    let mut runtime = detect_pipeline_runtime(app, args);
+    if let Some(node_path) = settings.node_path {
+        runtime.source = RuntimeSource::Configured;
+        runtime.node_cmd = PathBuf::from(node_path);
+    }
+    if let Some(working_dir) = settings.working_dir {
+        runtime.working_dir = PathBuf::from(working_dir);
+    }
+    runtime.env = settings.env;
+    runtime
+}
+
+// Tauri installs `externalBin` sidecars next to the main executable without the target triple.
+fn sidecar_node() -> Option<PathBuf> {
+    let name = if cfg!(target_os = "windows") {
+        "pipeline-node.exe"
+    } else {
+        "pipeline-node"
+    };
+    let path = std::env::current_exe().ok()?.parent()?.join(name);
+    path.is_file().then_some(path)
+}
+
+fn detect_pipeline_runtime(app: &tauri::AppHandle, args: &[String]) -> PipelineRuntime {
+    if let Ok(resource_dir) = app.path().resource_dir() {
+        let pipeline_candidates = [
+            resource_dir.join("pipeline.bundle.mjs"),
+            resource_dir.join("resources").join("pipeline.bundle.mjs"),
+        ];
+        let bundled_pipeline = pipeline_candidates.iter().find(|path| path.exists());
+        if let (Some(bundled_pipeline), Some(node_cmd)) = (bundled_pipeline, sidecar_node()) {
+            return PipelineRuntime {
+                source: RuntimeSource::Sidecar,
+                node_cmd,
+                script_path: bundled_pipeline.clone(),
+                use_tsx_loader: false,
+                working_dir: resolve_pipeline_working_dir(args),
+                env: BTreeMap::new(),
+            };
+        }
+    }
+
+    let workspace_root = project_root();
+    PipelineRuntime {
+        source: RuntimeSource::System,
+        node_cmd: PathBuf::from("node"),
+        script_path: workspace_root.join("scripts/pipeline.js"),
+        use_tsx_loader: true,
+        working_dir: workspace_root,
+        env: BTreeMap::new(),
+    }
+}
+
+fn append_unique_path(paths: &mut Vec<PathBuf>, candidate: PathBuf) {
+    if candidate.as_os_str().is_empty() {
+        return;
+    }
+
+    if !paths.iter().any(|existing| existing == &candidate) {
+        paths.push(candidate);
+    }
+}
+
+fn extend_paths_from_string(paths: &mut Vec<PathBuf>, value: &str) {
+    for candidate in std::env::split_paths(value) {
+        append_unique_path(paths, candidate);
+    }
+}
+
+fn resolve_login_shell_path() -> Option<String> {
+    #[cfg(unix)]
+    {
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string());
+        let output = Command::new(shell)
+            .args(["-lc", "printf %s \"$PATH\""])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if value.is_empty() {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+fn build_augmented_path_env() -> Option<String> {
+    let mut paths = Vec::new();
+
+    if let Ok(current_path) = std::env::var("PATH") {
+        extend_paths_from_string(&mut paths, &current_path);
+    }
+
+    if let Some(login_shell_path) = resolve_login_shell_path() {
+        extend_paths_from_string(&mut paths, &login_shell_path);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        for system_path in [
+            "/opt/homebrew/bin",
+            "/opt/homebrew/sbin",
+            "/usr/local/bin",
+            "/usr/local/sbin",
+            "/opt/local/bin",
+            "/opt/local/sbin",
+        ] {
+            append_unique_path(&mut paths, PathBuf::from(system_path));
+        }
+
+        if let Ok(home) = std::env::var("HOME") {
+            let home = PathBuf::from(home);
+            for suffix in [
+                ".local/bin",
+                ".npm-global/bin",
+                ".volta/bin",
+                ".asdf/shims",
+                ".nvm/current/bin",
+            ] {
+                append_unique_path(&mut paths, home.join(suffix));
+            }
+        }
+    }
+
+    std::env::join_paths(paths)
+        .ok()
+        .map(|joined| joined.to_string_lossy().to_string())
+}
+
+pub(crate) fn resolve_pipeline_path_env() -> Option<String> {
+    static CACHED_PATH: OnceLock<Option<String>> = OnceLock::new();
+    CACHED_PATH.get_or_init(build_augmented_path_env).clone()
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum PipelineMessage {
+    Progress { done: u64, total: u64 },
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+struct PipelineProgress {
+    pct: f64,
+    step: Option<String>,
+}
+
+// Scripts report progress as `##PROGRESS {"pct":42,"step":"compositing"}`; the older
+// `{"type":"progress","done":1,"total":4}` lines are still understood.
+const PROGRESS_PREFIX: &str = "##PROGRESS";
+const PROGRESS_ENV: &str = "DMA_PIPELINE_PROGRESS";
+const MAX_FINISHED_JOBS: usize = 50;
+
+#[derive(Clone, Serialize)]
+struct PipelineLine {
+    line: String,
+}
+
+#[derive(Clone, Serialize)]
+struct PipelineDone {
+    exit_code: Option<i32>,
+    success: bool,
+    cancelled: bool,
+    timed_out: bool,
+}
+
+#[derive(Clone, Serialize)]
+struct PipelineEvent<T> {
+    job_id: String,
+    #[serde(flatten)]
+    payload: T,
+}
+
+fn emit_pipeline_event<T: Serialize + Clone>(
+    app: &tauri::AppHandle,
+    event: &str,
+    job_id: &str,
+    payload: T,
+) {
+    let payload = PipelineEvent {
+        job_id: job_id.to_string(),
+        payload,
+    };
+    if let Err(error) = app.emit(event, payload) {
+        log::warn!("failed to emit {} event: {}", event, error);
+    }
+}
+
+// Reads until EOF, handing each line to `visit` both raw and with its line ending trimmed.
+fn for_each_line(reader: impl Read, mut visit: impl FnMut(&str, &str)) {
+    let mut reader = BufReader::new(reader);
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        match reader.read_until(b'\n', &mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+
+        let raw = String::from_utf8_lossy(&buffer);
+        visit(&raw, raw.trim_end_matches(['\r', '\n']));
+    }
+}
+
+fn parse_progress_line(line: &str) -> Option<PipelineProgress> {
+    let line = line.trim();
+    if let Some(payload) = line.strip_prefix(PROGRESS_PREFIX) {
+        let progress = serde_json::from_str::<PipelineProgress>(payload.trim()).ok()?;
+        return Some(PipelineProgress {
+            pct: progress.pct.clamp(0.0, 100.0),
+            step: progress.step,
+        });
+    }
+
+    match serde_json::from_str::<PipelineMessage>(line) {
+        Ok(PipelineMessage::Progress { done, total }) => Some(PipelineProgress {
+            pct: if total == 0 {
+                0.0
+            } else {
+                (done as f64 / total as f64 * 100.0).clamp(0.0, 100.0)
+            },
+            step: None,
+        }),
+        Err(_) => None,
+    }
+}
+
+fn strip_progress_lines(text: &str) -> String {
+    text.split_inclusive('\n')
+        .filter(|line| parse_progress_line(line).is_none())
+        .collect()
+}
+
+fn node_command(runtime: &PipelineRuntime) -> Command {
+    let mut process = Command::new(&runtime.node_cmd);
+    if let Some(path_env) = resolve_pipeline_path_env() {
+        process.env("PATH", path_env);
+    }
+    process.envs(&runtime.env);
+    process
+}
+
+fn build_pipeline_command(runtime: &PipelineRuntime, command: &str, args: &[String]) -> Command {
+    let mut process = node_command(runtime);
+
+    if runtime.use_tsx_loader {
+        process.arg("--import").arg("tsx");
+    }
+    process.env(PROGRESS_ENV, "1");
+
+    process
+        .arg(&runtime.script_path)
+        .arg(command)
+        .args(args)
+        .current_dir(&runtime.working_dir);
+    process
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PipelineError {
+    kind: &'static str,
+    message: String,
+    hint: Option<String>,
+    output: Option<Box<PipelineOutput>>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PipelineOutput {
+    exit_code: Option<i32>,
+    stdout: String,
+    stderr: String,
+    duration_ms: u64,
+    command_line: Vec<String>,
+}
+
+impl PipelineError {
+    fn new(kind: &'static str, message: String) -> Self {
+        Self {
+            kind,
+            message,
+            hint: None,
+            output: None,
+        }
+    }
+
+    fn with_output(mut self, output: PipelineOutput) -> Self {
+        self.output = Some(Box::new(output));
+        self
+    }
+}
+
+fn runtime_missing_hint() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "Install Node.js from nodejs.org or with `brew install node`, or set the runtime path in Settings."
+    } else if cfg!(target_os = "windows") {
+        "Install Node.js from nodejs.org or with `winget install OpenJS.NodeJS`, or set the runtime path in Settings."
+    } else {
+        "Install Node.js from nodejs.org or your package manager, or set the runtime path in Settings."
+    }
+}
+
+fn spawn_error(runtime: &PipelineRuntime, error: std::io::Error) -> PipelineError {
+    if error.kind() == std::io::ErrorKind::NotFound && !runtime.node_cmd.exists() {
+        return PipelineError {
+            kind: "runtime_missing",
+            message: format!(
+                "node runtime `{}` was not found",
+                runtime.node_cmd.display()
+            ),
+            hint: Some(runtime_missing_hint().to_string()),
+            output: None,
+        };
+    }
+
+    PipelineError::new(
+        "spawn_failed",
+        format!(
+            "failed to execute node `{}` with script `{}`: {}",
+            runtime.node_cmd.display(),
+            runtime.script_path.display(),
+            error
+        ),
+    )
+}
+
+fn cancelled_error(job_id: &str) -> PipelineError {
+    PipelineError::new(
+        "cancelled",
+        format!("pipeline job {} was cancelled", job_id),
+    )
+}
+
+fn timeout_error(limit: Duration, output: PipelineOutput) -> PipelineError {
+    let mut message = format!("pipeline timed out after {}s", limit.as_secs());
+    if !output.stderr.trim().is_empty() {
+        message.push('\n');
+        message.push_str(&output.stderr);
+    }
+    PipelineError::new("timeout", message).with_output(output)
+}
+
+fn command_line(process: &Command) -> Vec<String> {
+    std::iter::once(process.get_program())
+        .chain(process.get_args())
+        .map(|part| part.to_string_lossy().to_string())
+        .collect()
+}
+
+#[derive(Clone, Copy)]
+struct JobOptions {
+    stream_events: bool,
+    timeout: Option<Duration>,
+}
+
+impl JobOptions {
+    fn new(stream_events: bool, timeout_secs: Option<u64>) -> Self {
+        Self {
+            stream_events,
+            timeout: timeout_secs
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs),
+        }
+    }
+}
+
+// Kills the process tree once the limit passes unless `finish` is called first.
+struct Watchdog {
+    finished: mpsc::Sender<()>,
+    handle: std::thread::JoinHandle<bool>,
+}
+
+impl Watchdog {
+    fn start(pid: u32, limit: Duration) -> Self {
+        let (finished, receiver) = mpsc::channel::<()>();
+        let handle = std::thread::spawn(move || match receiver.recv_timeout(limit) {
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                kill_process_tree(pid);
+                true
+            }
+            _ => false,
+        });
+        Self { finished, handle }
+    }
+
+    fn finish(self) -> bool {
+        let _ = self.finished.send(());
+        self.handle.join().unwrap_or(false)
+    }
+}
+
+struct CollectedOutput {
+    status: ExitStatus,
+    stdout: String,
+    stderr: String,
+    timed_out: bool,
+}
+
+fn collect_with_events(
+    app: &tauri::AppHandle,
+    limiter: &PipelineLimiter,
+    job_id: &str,
+    mut child: Child,
+    watchdog: Option<Watchdog>,
+) -> Result<CollectedOutput, PipelineError> {
+    let stderr = child.stderr.take();
+    let stderr_app = app.clone();
+    let stderr_job = job_id.to_string();
+    let stderr_reader = std::thread::spawn(move || {
+        let mut collected = String::new();
+        if let Some(stderr) = stderr {
+            for_each_line(stderr, |raw, line| {
+                collected.push_str(raw);
+                let payload = PipelineLine {
+                    line: line.to_string(),
+                };
+                emit_pipeline_event(&stderr_app, "pipeline://stderr", &stderr_job, payload);
+            });
+        }
+        collected
+    });
+
+    let mut stdout_text = String::new();
+    if let Some(stdout) = child.stdout.take() {
+        for_each_line(stdout, |raw, line| {
+            if let Some(progress) = parse_progress_line(line) {
+                emit_pipeline_event(app, "pipeline://progress", job_id, progress);
+                return;
+            }
+
+            stdout_text.push_str(raw);
+            let payload = PipelineLine {
+                line: line.to_string(),
+            };
+            emit_pipeline_event(app, "pipeline://stdout", job_id, payload);
+        });
+    }
+
+    let status = child.wait().map_err(|error| {
+        PipelineError::new(
+            "spawn_failed",
+            format!("failed to wait for pipeline process: {}", error),
+        )
+    })?;
+    let stderr_text = stderr_reader.join().unwrap_or_default();
+    let timed_out = watchdog.map(Watchdog::finish).unwrap_or(false);
+    let done = PipelineDone {
+        exit_code: status.code(),
+        success: status.success(),
+        cancelled: limiter.is_cancelled(job_id),
+        timed_out,
+    };
+    emit_pipeline_event(app, "pipeline://done", job_id, done);
+
+    Ok(CollectedOutput {
+        status,
+        stdout: stdout_text,
+        stderr: stderr_text,
+        timed_out,
+    })
+}
+
+fn verify_pipeline_script(runtime: &PipelineRuntime) -> Result<PathBuf, PipelineError> {
+    let script = &runtime.script_path;
+    match std::fs::File::open(script) {
+        Ok(_) if script.is_file() => Ok(script.clone()),
+        Ok(_) => Err(PipelineError::new(
+            "script_missing",
+            format!("pipeline script `{}` is not a file", script.display()),
+        )),
+        Err(error) => Err(PipelineError {
+            kind: "script_missing",
+            message: format!(
+                "pipeline script `{}` is not readable: {}",
+                script.display(),
+                error
+            ),
+            hint: Some(
+                "Reinstall the app or restore scripts/pipeline.js in the workspace.".to_string(),
+            ),
+            output: None,
+        }),
+    }
+}
+
+#[tauri::command]
+pub fn check_pipeline_script(app: tauri::AppHandle) -> Result<String, PipelineError> {
+    let runtime = resolve_pipeline_runtime(&app, &[]);
+    verify_pipeline_script(&runtime).map(|script| script.to_string_lossy().replace('\\', "/"))
+}
+
+#[derive(Clone, Serialize)]
+struct PipelineQueued {
+    job_id: String,
+    position: usize,
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum PipelineJobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Clone, Serialize)]
+pub struct PipelineJob {
+    job_id: String,
+    command: String,
+    status: PipelineJobStatus,
+    error: Option<String>,
+}
+
+impl PipelineJob {
+    fn is_finished(&self) -> bool {
+        matches!(
+            self.status,
+            PipelineJobStatus::Done | PipelineJobStatus::Failed
+        )
+    }
+}
+
+#[derive(Deserialize)]
+pub struct PipelineJobRequest {
+    command: String,
+    args: Vec<String>,
+    timeout_secs: Option<u64>,
+}
+
+struct LimiterState {
+    max_running: usize,
+    running: usize,
+    queue: VecDeque<String>,
+    cancelled: HashSet<String>,
+    // Jobs that hold a permit, with the process id once the node child has been spawned.
+    processes: HashMap<String, Option<u32>>,
+    // Jobs started through start_pipeline/enqueue_pipeline_jobs; run_pipeline returns its result
+    // directly and is not tracked here.
+    jobs: Vec<PipelineJob>,
+}
+
+pub struct PipelineLimiter {
+    next_job: AtomicU64,
+    state: Mutex<LimiterState>,
+    changed: Condvar,
+}
+
+impl Default for PipelineLimiter {
+    fn default() -> Self {
+        let max_running = std::thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(1);
+        Self {
+            next_job: AtomicU64::new(1),
+            state: Mutex::new(LimiterState {
+                max_running,
+                running: 0,
+                queue: VecDeque::new(),
+                cancelled: HashSet::new(),
+                processes: HashMap::new(),
+                jobs: Vec::new(),
+            }),
+            changed: Condvar::new(),
+        }
+    }
+}
+
+struct PipelinePermit<'a> {
+    limiter: &'a PipelineLimiter,
+    job_id: String,
+}
+
+impl Drop for PipelinePermit<'_> {
+    fn drop(&mut self) {
+        let mut state = self.limiter.lock_state();
+        state.running = state.running.saturating_sub(1);
+        state.processes.remove(&self.job_id);
+        state.cancelled.remove(&self.job_id);
+        drop(state);
+        self.limiter.changed.notify_all();
+    }
+}
+
+// The node child leads its own process group on unix so killing the group also stops anything it
+// spawned; on Windows taskkill /T walks the tree instead.
+fn kill_process_tree(pid: u32) {
+    #[cfg(unix)]
+    let status = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", pid)])
+        .status();
+    #[cfg(windows)]
+    let status = {
+        use std::os::windows::process::CommandExt;
+        Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .creation_flags(0x0800_0000)
+            .status()
+    };
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => log::warn!("failed to kill pipeline process {}: {}", pid, status),
+        Err(error) => log::warn!("failed to kill pipeline process {}: {}", pid, error),
+    }
+}
+
+impl PipelineLimiter {
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, LimiterState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn next_job_id(&self) -> String {
+        format!("job-{}", self.next_job.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn set_job_status(
+        state: &mut LimiterState,
+        job_id: &str,
+        status: PipelineJobStatus,
+        error: Option<String>,
+    ) {
+        if let Some(job) = state.jobs.iter_mut().find(|job| job.job_id == job_id) {
+            job.status = status;
+            job.error = error;
+        }
+    }
+
+    fn register_job(&self, job_id: &str, command: &str) {
+        let mut state = self.lock_state();
+        if state.jobs.iter().any(|job| job.job_id == job_id) {
+            Self::set_job_status(&mut state, job_id, PipelineJobStatus::Queued, None);
+            return;
+        }
+        state.jobs.push(PipelineJob {
+            job_id: job_id.to_string(),
+            command: command.to_string(),
+            status: PipelineJobStatus::Queued,
+            error: None,
+        });
+    }
+
+    // Only the error message is kept; the full output was already returned or emitted.
+    fn complete_job(&self, job_id: &str, result: &Result<PipelineOutput, PipelineError>) {
+        let (status, error) = match result {
+            Ok(_) => (PipelineJobStatus::Done, None),
+            Err(error) => (PipelineJobStatus::Failed, Some(error.message.clone())),
+        };
+        let mut state = self.lock_state();
+        Self::set_job_status(&mut state, job_id, status, error);
+
+        let finished = state.jobs.iter().filter(|job| job.is_finished()).count();
+        let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+        state.jobs.retain(|job| {
+            if excess > 0 && job.is_finished() {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+    }
+
+    fn cancel(&self, job_id: String) -> bool {
+        let mut state = self.lock_state();
+        let queued = state.queue.iter().any(|queued| queued == &job_id);
+        let process = match state.processes.get(&job_id) {
+            Some(process) => *process,
+            None if queued => None,
+            None => return false,
+        };
+
+        state.cancelled.insert(job_id);
+        drop(state);
+        self.changed.notify_all();
+        // kill/taskkill is waited on, so it runs after the limiter lock is released.
+        if let Some(pid) = process {
+            kill_process_tree(pid);
+        }
+        true
+    }
+
+    // Removes and returns finished jobs; queued and running jobs stay listed.
+    fn drain_finished(&self) -> Vec<PipelineJob> {
+        let mut state = self.lock_state();
+        let (finished, pending) = std::mem::take(&mut state.jobs)
+            .into_iter()
+            .partition(PipelineJob::is_finished);
+        state.jobs = pending;
+        finished
+    }
+
+    fn is_cancelled(&self, job_id: &str) -> bool {
+        self.lock_state().cancelled.contains(job_id)
+    }
+
+    fn spawn_tracked(
+        &self,
+        job_id: &str,
+        runtime: &PipelineRuntime,
+        mut process: Command,
+    ) -> Result<Child, PipelineError> {
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut process, 0);
+
+        let mut state = self.lock_state();
+        if state.cancelled.contains(job_id) {
+            return Err(cancelled_error(job_id));
+        }
+        let child = process
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| spawn_error(runtime, error))?;
+        state.processes.insert(job_id.to_string(), Some(child.id()));
+        Ok(child)
+    }
+
+    // `report_position` is called with the 1-based queue position whenever it changes.
+    fn acquire(
+        &self,
+        job_id: &str,
+        mut report_position: impl FnMut(usize),
+    ) -> Result<PipelinePermit<'_>, PipelineError> {
+        let mut state = self.lock_state();
+        state.queue.push_back(job_id.to_string());
+        let mut reported = None;
+        loop {
+            if state.cancelled.remove(job_id) {
+                state.queue.retain(|queued| queued != job_id);
+                drop(state);
+                self.changed.notify_all();
+                return Err(PipelineError::new(
+                    "cancelled",
+                    format!("pipeline job {} was cancelled before it started", job_id),
+                ));
+            }
+
+            let position = state
+                .queue
+                .iter()
+                .position(|queued| queued == job_id)
+                .unwrap_or(0);
+            if position == 0 && state.running < state.max_running {
+                state.queue.pop_front();
+                state.running += 1;
+                state.processes.insert(job_id.to_string(), None);
+                Self::set_job_status(&mut state, job_id, PipelineJobStatus::Running, None);
+                drop(state);
+                self.changed.notify_all();
+                return Ok(PipelinePermit {
+                    limiter: self,
+                    job_id: job_id.to_string(),
+                });
+            }
+
+            if reported != Some(position) {
+                reported = Some(position);
+                report_position(position + 1);
+            }
+            state = self
+                .changed
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+}
+
+#[tauri::command]
+pub fn cancel_pipeline(limiter: tauri::State<'_, PipelineLimiter>, job_id: String) -> bool {
+    limiter.cancel(job_id)
+}
+
+#[tauri::command]
+pub fn set_pipeline_concurrency(
+    limiter: tauri::State<'_, PipelineLimiter>,
+    max: usize,
+) -> Result<(), String> {
+    if max == 0 {
+        return Err("pipeline concurrency must be at least 1".to_string());
+    }
+
+    limiter.lock_state().max_running = max;
+    limiter.changed.notify_all();
+    Ok(())
+}
+
+fn execute_pipeline(
+    app: &tauri::AppHandle,
+    runtime: &PipelineRuntime,
+    command: &str,
+    args: &[String],
+    job_id: &str,
+    options: JobOptions,
+) -> Result<PipelineOutput, PipelineError> {
+    let limiter = app.state::<PipelineLimiter>();
+    let result = run_job(app, &limiter, runtime, command, args, job_id, options);
+    limiter.complete_job(job_id, &result);
+    result
+}
+
+fn run_job(
+    app: &tauri::AppHandle,
+    limiter: &PipelineLimiter,
+    runtime: &PipelineRuntime,
+    command: &str,
+    args: &[String],
+    job_id: &str,
+    options: JobOptions,
+) -> Result<PipelineOutput, PipelineError> {
+    verify_pipeline_script(runtime)?;
+    let _permit = limiter.acquire(job_id, |position| {
+        let payload = PipelineQueued {
+            job_id: job_id.to_string(),
+            position,
+        };
+        if let Err(error) = app.emit("pipeline://queued", payload) {
+            log::warn!("failed to emit pipeline queued event: {}", error);
+        }
+    })?;
+
+    let process = build_pipeline_command(runtime, command, args);
+    let command_line = command_line(&process);
+    let started = Instant::now();
+    let child = limiter.spawn_tracked(job_id, runtime, process)?;
+    let watchdog = options
+        .timeout
+        .map(|limit| Watchdog::start(child.id(), limit));
+    let collected = if options.stream_events {
+        collect_with_events(app, limiter, job_id, child, watchdog)?
+    } else {
+        let output = child.wait_with_output().map_err(|error| {
+            PipelineError::new(
+                "spawn_failed",
+                format!("failed to wait for pipeline process: {}", error),
+            )
+        })?;
+        CollectedOutput {
+            status: output.status,
+            stdout: strip_progress_lines(&String::from_utf8_lossy(&output.stdout)),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            timed_out: watchdog.map(Watchdog::finish).unwrap_or(false),
+        }
+    };
+
+    let output = PipelineOutput {
+        exit_code: collected.status.code(),
+        stdout: collected.stdout,
+        stderr: collected.stderr,
+        duration_ms: started.elapsed().as_millis() as u64,
+        command_line,
+    };
+    if limiter.is_cancelled(job_id) {
+        return Err(cancelled_error(job_id).with_output(output));
+    }
+    if collected.timed_out {
+        return Err(timeout_error(options.timeout.unwrap_or_default(), output));
+    }
+    if !collected.status.success() {
+        return Err(
+            PipelineError::new("pipeline_failed", output.stderr.clone()).with_output(output)
+        );
+    }
+
+    Ok(output)
+}
+
+fn log_pipeline_failure(command: &str, error: &PipelineError) {
+    log::error!(
+        "pipeline `{}` failed ({}): {}",
+        command,
+        error.kind,
+        error.message.trim()
+    )
+}
+
+#[tauri::command]
+pub async fn run_pipeline(
+    app: tauri::AppHandle,
+    command: String,
+    args: Vec<String>,
+    progress_events: Option<bool>,
+    job_id: Option<String>,
+    timeout_secs: Option<u64>,
+) -> Result<PipelineOutput, PipelineError> {
+    let runtime = resolve_pipeline_runtime(&app, &args);
+    let command_name = command.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let job_id = job_id
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| app.state::<PipelineLimiter>().next_job_id());
+        execute_pipeline(
+            &app,
+            &runtime,
+            &command,
+            &args,
+            &job_id,
+            JobOptions::new(progress_events.unwrap_or(false), timeout_secs),
+        )
+    })
+    .await
+    .map_err(|error| {
+        PipelineError::new(
+            "internal",
+            format!("failed to join run_pipeline task: {}", error),
+        )
+    })?
+    .inspect_err(|error| log_pipeline_failure(&command_name, error))
+}
+
+fn spawn_job(
+    app: &tauri::AppHandle,
+    command: String,
+    args: Vec<String>,
+    timeout_secs: Option<u64>,
+) -> String {
+    let runtime = resolve_pipeline_runtime(app, &args);
+    let limiter = app.state::<PipelineLimiter>();
+    let job_id = limiter.next_job_id();
+    // Registering before the task starts keeps the job visible to list_pipeline_jobs right away.
+    limiter.register_job(&job_id, &command);
+
+    let app = app.clone();
+    let background_job = job_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(error) = execute_pipeline(
+            &app,
+            &runtime,
+            &command,
+            &args,
+            &background_job,
+            JobOptions::new(true, timeout_secs),
+        ) {
+            log_pipeline_failure(&command, &error);
+            emit_pipeline_event(&app, "pipeline://error", &background_job, error);
+        }
+    });
+    job_id
+}
+
+// Output, progress and completion arrive as pipeline:// events tagged with the returned job id;
+// failures are reported through pipeline://error.
+#[tauri::command]
+pub fn start_pipeline(
+    app: tauri::AppHandle,
+    command: String,
+    args: Vec<String>,
+    timeout_secs: Option<u64>,
+) -> String {
+    spawn_job(&app, command, args, timeout_secs)
+}
+
+#[tauri::command]
+pub fn enqueue_pipeline_jobs(app: tauri::AppHandle, jobs: Vec<PipelineJobRequest>) -> Vec<String> {
+    jobs.into_iter()
+        .map(|job| spawn_job(&app, job.command, job.args, job.timeout_secs))
+        .collect()
+}
+
+#[tauri::command]
+pub fn list_pipeline_jobs(limiter: tauri::State<'_, PipelineLimiter>) -> Vec<PipelineJob> {
+    limiter.lock_state().jobs.clone()
+}
+
+#[tauri::command]
+pub fn drain_pipeline_jobs(limiter: tauri::State<'_, PipelineLimiter>) -> Vec<PipelineJob> {
+    limiter.drain_finished()
+}
+
+#[derive(Serialize)]
+pub struct PipelineRuntimeInfo {
+    source: RuntimeSource,
+    node_cmd: String,
+    script_path: String,
+    use_tsx_loader: bool,
+    working_dir: String,
+    script_available: bool,
+}
+
+#[tauri::command]
+pub fn check_pipeline_runtime(app: tauri::AppHandle) -> PipelineRuntimeInfo {
+    let runtime = resolve_pipeline_runtime(&app, &[]);
+    PipelineRuntimeInfo {
+        source: runtime.source,
+        node_cmd: runtime.node_cmd.to_string_lossy().replace('\\', "/"),
+        script_path: runtime.script_path.to_string_lossy().replace('\\', "/"),
+        use_tsx_loader: runtime.use_tsx_loader,
+        working_dir: runtime.working_dir.to_string_lossy().replace('\\', "/"),
+        script_available: verify_pipeline_script(&runtime).is_ok(),
+    }
+}
+
+#[derive(Serialize)]
+pub struct PipelineProblem {
+    kind: &'static str,
+    message: String,
+    path: Option<String>,
+}
+
+impl PipelineProblem {
+    fn new(kind: &'static str, message: String, path: Option<&Path>) -> Self {
+        Self {
+            kind,
+            message,
+            path: path.map(|path| path.to_string_lossy().replace('\\', "/")),
+        }
+    }
+}
+
+#[derive(Default)]
+struct PipelineTargets {
+    project: Option<PathBuf>,
+    inputs: Vec<PathBuf>,
+    outputs: Vec<PathBuf>,
+}
+
+fn default_output(project: &Path, name: &str) -> PathBuf {
+    project.parent().unwrap_or(Path::new("")).join(name)
+}
+
+// Mirrors the positional arguments and default output dirs used by scripts/pipeline.js.
+fn pipeline_targets(
+    runtime: &PipelineRuntime,
+    command: &str,
+    args: &[String],
+) -> Result<PipelineTargets, PipelineProblem> {
+    let resolve = |index: usize| args.get(index).map(|value| runtime.working_dir.join(value));
+    let required = |index: usize, name: &str| {
+        resolve(index).ok_or_else(|| {
+            PipelineProblem::new(
+                "missing_argument",
+                format!("`{}` requires a {} argument", command, name),
+                None,
+            )
+        })
+    };
+
+    let mut targets = PipelineTargets::default();
+    match command {
+        "validate" | "localize" => {
+            targets.project = Some(required(0, "project path")?);
+        }
+        "render" => {
+            let project = required(0, "project path")?;
+            targets
+                .outputs
+                .push(resolve(1).unwrap_or_else(|| default_output(&project, "dist-render")));
+            targets.project = Some(project);
+        }
+        "export" => {
+            let project = required(0, "project path")?;
+            targets.inputs.push(required(1, "render directory")?);
+            targets
+                .outputs
+                .push(resolve(2).unwrap_or_else(|| default_output(&project, "dist")));
+            targets.project = Some(project);
+        }
+        "all" => {
+            let project = required(0, "project path")?;
+            let base = resolve(1).unwrap_or_else(|| default_output(&project, "dist"));
+            let mut render_dir = base.clone().into_os_string();
+            render_dir.push("-render");
+            targets.outputs.push(PathBuf::from(render_dir));
+            targets.outputs.push(base);
+            targets.project = Some(project);
+        }
+        "upload" => {
+            targets.inputs.push(required(0, "export directory")?);
+            if args.len() < 2 {
+                return Err(PipelineProblem::new(
+                    "missing_argument",
+                    "`upload` requires an iOS or Android lane".to_string(),
+                    None,
+                ));
+            }
+        }
+        _ => {
+            return Err(PipelineProblem::new(
+                "unknown_command",
+                format!("unknown pipeline command `{}`", command),
+                None,
+            ));
+        }
+    }
+    Ok(targets)
+}
+
+fn check_node(runtime: &PipelineRuntime) -> Option<PipelineProblem> {
+    match node_command(runtime).arg("--version").output() {
+        Ok(output) if output.status.success() => None,
+        Ok(output) => Some(PipelineProblem::new(
+            "node_unavailable",
+            format!(
+                "`{} --version` failed: {}",
+                runtime.node_cmd.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Some(&runtime.node_cmd),
+        )),
+        Err(error) => {
+            let error = spawn_error(runtime, error);
+            Some(PipelineProblem::new(
+                error.kind,
+                error.message,
+                Some(&runtime.node_cmd),
+            ))
+        }
+    }
+}
+
+// Asset references are resolved against the project file's directory, like the node packages do.
+fn check_project_assets(project: &Path, problems: &mut Vec<PipelineProblem>) {
+    let document = std::fs::read_to_string(project)
+        .map_err(|error| format!("failed to read project: {}", error))
+        .and_then(|raw| {
+            serde_json::from_str::<serde_json::Value>(&raw)
+                .map_err(|error| format!("failed to parse project: {}", error))
+        });
+    let mut document = match document {
+        Ok(document) => document,
+        Err(message) => {
+            problems.push(PipelineProblem::new(
+                "invalid_project",
+                message,
+                Some(project),
+            ));
+            return;
+        }
+    };
+
+    let project_dir = project.parent().unwrap_or(Path::new(""));
+    let mut seen = HashSet::new();
+    crate::bundle::visit_asset_references(&mut document, &mut |reference| {
+        let source = project_dir.join(reference.trim());
+        if seen.insert(source.clone()) && !source.exists() {
+            problems.push(PipelineProblem::new(
+                "missing_input",
+                format!("referenced file not found: {}", reference),
+                Some(&source),
+            ));
+        }
+    });
+}
+
+// Output dirs are created by the pipeline, so the nearest existing ancestor has to be writable.
+fn check_output_dir(output: &Path) -> Option<PipelineProblem> {
+    let existing = output.ancestors().find(|path| path.exists())?;
+    crate::probe_directory_write(existing)
+        .err()
+        .map(|message| PipelineProblem::new("output_not_writable", message, Some(output)))
+}
+
+#[tauri::command]
+pub async fn validate_pipeline(
+    app: tauri::AppHandle,
+    command: String,
+    args: Vec<String>,
+) -> Result<Vec<PipelineProblem>, PipelineError> {
+    let runtime = resolve_pipeline_runtime(&app, &args);
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut problems = Vec::new();
+        if let Err(error) = verify_pipeline_script(&runtime) {
+            problems.push(PipelineProblem::new(
+                error.kind,
+                error.message,
+                Some(&runtime.script_path),
+            ));
+        }
+        problems.extend(check_node(&runtime));
+
+        let targets = match pipeline_targets(&runtime, &command, &args) {
+            Ok(targets) => targets,
+            Err(problem) => {
+                problems.push(problem);
+                return problems;
+            }
+        };
+
+        if let Some(project) = &targets.project {
+            if project.is_file() {
+                check_project_assets(project, &mut problems);
+            } else {
+                problems.push(PipelineProblem::new(
+                    "missing_input",
+                    format!("project file not found: {}", project.display()),
+                    Some(project),
+                ));
+            }
+        }
+        for input in &targets.inputs {
+            if !input.is_dir() {
+                problems.push(PipelineProblem::new(
+                    "missing_input",
+                    format!("directory not found: {}", input.display()),
+                    Some(input),
+                ));
+            }
+        }
+        for output in &targets.outputs {
+            problems.extend(check_output_dir(output));
+        }
+        problems
+    })
+    .await
+    .map_err(|error| {
+        PipelineError::new(
+            "internal",
+            format!("failed to join validate_pipeline task: {}", error),
+        )
+    })
+}
+
+#[derive(Serialize)]
+pub struct RuntimeInfo {
+    node_cmd: String,
+    version: String,
+}
+
+#[tauri::command]
+pub async fn check_runtime(app: tauri::AppHandle) -> Result<RuntimeInfo, PipelineError> {
+    let runtime = resolve_pipeline_runtime(&app, &[]);
+    tauri::async_runtime::spawn_blocking(move || {
+        let output = node_command(&runtime)
+            .arg("--version")
+            .output()
+            .map_err(|error| spawn_error(&runtime, error))?;
+        if !output.status.success() {
+            return Err(PipelineError::new(
+                "spawn_failed",
+                format!(
+                    "`{} --version` failed: {}",
+                    runtime.node_cmd.display(),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            ));
+        }
+
+        Ok(RuntimeInfo {
+            node_cmd: runtime.node_cmd.to_string_lossy().to_string(),
+            version: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        })
+    })
+    .await
+    .map_err(|error| {
+        PipelineError::new(
+            "internal",
+            format!("failed to join check_runtime task: {}", error),
+        )
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn single_slot_limiter() -> PipelineLimiter {
+        let limiter = PipelineLimiter::default();
+        limiter.lock_state().max_running = 1;
+        limiter
+    }
+
+    fn output() -> PipelineOutput {
+        PipelineOutput {
+            exit_code: Some(1),
+            stdout: "x".repeat(4096),
+            stderr: "boom".to_string(),
+            duration_ms: 5,
+            command_line: vec!["node".to_string()],
+        }
+    }
+
+    #[test]
+    fn progress_lines_are_parsed_and_clamped() {
+        let progress =
+            parse_progress_line("  ##PROGRESS {\"pct\":42.5,\"step\":\"compositing\"}\r\n")
+                .unwrap();
+        assert_eq!(progress.pct, 42.5);
+        assert_eq!(progress.step.as_deref(), Some("compositing"));
+
+        assert_eq!(
+            parse_progress_line("##PROGRESS {\"pct\":140}").unwrap().pct,
+            100.0
+        );
+        assert_eq!(
+            parse_progress_line("##PROGRESS {\"pct\":-3}").unwrap().pct,
+            0.0
+        );
+        assert!(parse_progress_line("##PROGRESS not json").is_none());
+        assert!(parse_progress_line("rendered home.png").is_none());
+    }
+
+    #[test]
+    fn legacy_progress_lines_convert_to_percent() {
+        let progress =
+            parse_progress_line("{\"type\":\"progress\",\"done\":1,\"total\":4}").unwrap();
+        assert_eq!(progress.pct, 25.0);
+        assert!(progress.step.is_none());
+
+        let empty = parse_progress_line("{\"type\":\"progress\",\"done\":0,\"total\":0}").unwrap();
+        assert_eq!(empty.pct, 0.0);
+        let over = parse_progress_line("{\"type\":\"progress\",\"done\":5,\"total\":4}").unwrap();
+        assert_eq!(over.pct, 100.0);
+        assert!(parse_progress_line("{\"type\":\"log\",\"message\":\"hi\"}").is_none());
+    }
+
+    #[test]
+    fn progress_lines_are_stripped_from_collected_stdout() {
+        let stdout =
+            "start\n##PROGRESS {\"pct\":50}\n{\"type\":\"progress\",\"done\":1,\"total\":2}\r\nend";
+        assert_eq!(strip_progress_lines(stdout), "start\nend");
+    }
+
+    #[test]
+    fn queued_jobs_wait_for_a_free_slot() {
+        let limiter = single_slot_limiter();
+        let first = limiter.acquire("job-a", |_| {}).unwrap();
+
+        std::thread::scope(|scope| {
+            let (positions, reported) = mpsc::channel();
+            let waiting = scope.spawn(|| {
+                let positions = positions;
+                limiter
+                    .acquire("job-b", move |position| positions.send(position).unwrap())
+                    .map(|_| ())
+            });
+
+            assert_eq!(reported.recv().unwrap(), 1);
+            assert_eq!(limiter.lock_state().running, 1);
+            assert!(!waiting.is_finished());
+
+            drop(first);
+            assert!(waiting.join().unwrap().is_ok());
+        });
+
+        let state = limiter.lock_state();
+        assert_eq!(state.running, 0);
+        assert!(state.queue.is_empty());
+        assert!(state.processes.is_empty());
+    }
+
+    #[test]
+    fn cancelling_a_queued_job_fails_its_acquire() {
+        let limiter = single_slot_limiter();
+        let _first = limiter.acquire("job-a", |_| {}).unwrap();
+
+        std::thread::scope(|scope| {
+            let (positions, reported) = mpsc::channel();
+            let waiting = scope.spawn(|| {
+                let positions = positions;
+                limiter
+                    .acquire("job-b", move |position| positions.send(position).unwrap())
+                    .map(|_| ())
+            });
+
+            reported.recv().unwrap();
+            assert!(limiter.cancel("job-b".to_string()));
+            let error = waiting.join().unwrap().unwrap_err();
+            assert_eq!(error.kind, "cancelled");
+        });
+
+        let state = limiter.lock_state();
+        assert!(state.queue.is_empty());
+        assert!(state.cancelled.is_empty());
+        assert_eq!(state.running, 1);
+    }
+
+    #[test]
+    fn cancelling_marks_running_jobs_until_their_permit_drops() {
+        let limiter = single_slot_limiter();
+        assert!(!limiter.cancel("job-missing".to_string()));
+
+        let permit = limiter.acquire("job-a", |_| {}).unwrap();
+        assert!(limiter.cancel("job-a".to_string()));
+        assert!(limiter.is_cancelled("job-a"));
+
+        drop(permit);
+        assert!(!limiter.is_cancelled("job-a"));
+        assert_eq!(limiter.lock_state().running, 0);
+        assert!(!limiter.cancel("job-a".to_string()));
+    }
+
+    #[test]
+    fn finished_jobs_keep_only_the_error_message_and_are_capped() {
+        let limiter = PipelineLimiter::default();
+        limiter.register_job("job-queued", "render");
+        for index in 0..MAX_FINISHED_JOBS + 5 {
+            let job_id = format!("job-{}", index);
+            limiter.register_job(&job_id, "render");
+            let result = if index == MAX_FINISHED_JOBS + 4 {
+                Err(PipelineError::new("pipeline_failed", "boom".to_string()).with_output(output()))
+            } else {
+                Ok(output())
+            };
+            limiter.complete_job(&job_id, &result);
+        }
+
+        {
+            let state = limiter.lock_state();
+            assert_eq!(state.jobs.len(), MAX_FINISHED_JOBS + 1);
+            assert_eq!(state.jobs[0].job_id, "job-queued");
+            assert_eq!(state.jobs[1].job_id, "job-5");
+            let failed = state.jobs.last().unwrap();
+            assert!(matches!(failed.status, PipelineJobStatus::Failed));
+            assert_eq!(failed.error.as_deref(), Some("boom"));
+        }
+
+        let drained = limiter.drain_finished();
+        assert_eq!(drained.len(), MAX_FINISHED_JOBS);
+        let state = limiter.lock_state();
+        assert_eq!(state.jobs.len(), 1);
+        assert!(matches!(state.jobs[0].status, PipelineJobStatus::Queued));
+    }
+
+    #[test]
+    fn completing_an_untracked_job_does_not_record_it() {
+        let limiter = PipelineLimiter::default();
+        limiter.complete_job("job-sync", &Ok(output()));
+        assert!(limiter.lock_state().jobs.is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::Manager;
+
+use crate::config_store;
+
+const PIPELINE_SETTINGS_FILE: &str = "pipeline-settings.json";
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PipelineSettings {
+    pub(crate) node_path: Option<String>,
+    pub(crate) env: BTreeMap<String, String>,
+    pub(crate) working_dir: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct NodeInstall {
+    path: String,
+    source: &'static str,
+    version: Option<String>,
+}
+
+pub(crate) fn load_pipeline_settings(app: &tauri::AppHandle) -> PipelineSettings {
+    config_store::load_json(app, PIPELINE_SETTINGS_FILE).unwrap_or_default()
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn normalize_settings(settings: PipelineSettings) -> Result<PipelineSettings, String> {
+    let node_path = non_empty(settings.node_path);
+    if let Some(node_path) = &node_path {
+        if !Path::new(node_path).is_file() {
+            return Err(format!("node executable not found: {}", node_path));
+        }
+    }
+
+    let working_dir = non_empty(settings.working_dir);
+    if let Some(working_dir) = &working_dir {
+        if !Path::new(working_dir).is_dir() {
+            return Err(format!("working directory not found: {}", working_dir));
+        }
+    }
+
+    let mut env = BTreeMap::new();
+    for (key, value) in settings.env {
+        let key = key.trim().to_string();
+        if key.is_empty() || key.contains('=') || key.contains('\0') || value.contains('\0') {
+            return Err(format!("invalid environment variable name: {:?}", key));
+        }
+        env.insert(key, value);
+    }
+
+    Ok(PipelineSettings {
+        node_path,
+        env,
+        working_dir,
+    })
+}
+
+#[tauri::command]
+pub fn get_pipeline_settings(app: tauri::AppHandle) -> PipelineSettings {
+    load_pipeline_settings(&app)
+}
+
+#[tauri::command]
+pub fn set_pipeline_settings(
+    app: tauri::AppHandle,
+    settings: PipelineSettings,
+) -> Result<PipelineSettings, String> {
+    let settings = normalize_settings(settings)?;
+    config_store::save_json(&app, PIPELINE_SETTINGS_FILE, &settings)?;
+    Ok(settings)
+}
+
+fn node_binary_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "node.exe"
+    } else {
+        "node"
+    }
+}
+
+// Version managers keep one directory per installed version; newest-looking names come first.
+fn versioned_installs(root: &Path, suffix: &[&str]) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut versions = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect::<Vec<PathBuf>>();
+    versions.sort_by(|left, right| {
+        crate::natural_cmp(&right.to_string_lossy(), &left.to_string_lossy())
+    });
+    versions
+        .into_iter()
+        .map(|version| suffix.iter().fold(version, |path, part| path.join(part)))
+        .collect()
+}
+
+fn candidate_installs(home: Option<&Path>) -> Vec<(PathBuf, &'static str)> {
+    let node = node_binary_name();
+    let bin_node: &[&str] = if cfg!(target_os = "windows") {
+        &[node]
+    } else {
+        &["bin", node]
+    };
+    let mut candidates = Vec::new();
+
+    if let Some(path_env) = crate::pipeline::resolve_pipeline_path_env() {
+        for directory in std::env::split_paths(&path_env) {
+            candidates.push((directory.join(node), "path"));
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    for prefix in ["/opt/homebrew", "/usr/local"] {
+        let prefix = Path::new(prefix);
+        candidates.push((prefix.join("bin").join(node), "homebrew"));
+        if let Ok(entries) = fs::read_dir(prefix.join("opt")) {
+            let mut formulae = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name == "node" || name.starts_with("node@"))
+                })
+                .collect::<Vec<PathBuf>>();
+            formulae.sort();
+            for formula in formulae {
+                candidates.push((formula.join("bin").join(node), "homebrew"));
+            }
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(nvm_home) = std::env::var_os("NVM_HOME") {
+            for path in versioned_installs(Path::new(&nvm_home), &[node]) {
+                candidates.push((path, "nvm"));
+            }
+        }
+        if let Some(program_files) = std::env::var_os("ProgramFiles") {
+            candidates.push((
+                Path::new(&program_files).join("nodejs").join(node),
+                "system",
+            ));
+        }
+    }
+
+    let nvm_dir = std::env::var_os("NVM_DIR")
+        .map(PathBuf::from)
+        .or_else(|| home.map(|home| home.join(".nvm")));
+    if let Some(nvm_dir) = nvm_dir {
+        for path in versioned_installs(&nvm_dir.join("versions").join("node"), bin_node) {
+            candidates.push((path, "nvm"));
+        }
+    }
+
+    let volta_home = std::env::var_os("VOLTA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.map(|home| home.join(".volta")));
+    if let Some(volta_home) = volta_home {
+        let images = volta_home.join("tools").join("image").join("node");
+        for path in versioned_installs(&images, bin_node) {
+            candidates.push((path, "volta"));
+        }
+    }
+
+    if let Some(home) = home {
+        let installs = home.join(".asdf").join("installs").join("nodejs");
+        for path in versioned_installs(&installs, bin_node) {
+            candidates.push((path, "asdf"));
+        }
+    }
+
+    candidates
+}
+
+fn node_version(path: &Path) -> Option<String> {
+    let output = Command::new(path).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
+#[tauri::command]
+pub async fn detect_node_installs(app: tauri::AppHandle) -> Result<Vec<NodeInstall>, String> {
+    let home = app.path().home_dir().ok();
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut seen = HashSet::new();
+        let mut installs = Vec::new();
+        for (path, source) in candidate_installs(home.as_deref()) {
+            if !path.is_file() {
+                continue;
+            }
+            // Shims and symlinked bin dirs often point at the same binary under several names.
+            let canonical = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if !seen.insert(canonical) {
+                continue;
+            }
+
+            installs.push(NodeInstall {
+                path: path.to_string_lossy().replace('\\', "/"),
+                source,
+                version: node_version(&path),
+            });
+        }
+        installs
+    })
+    .await
+    .map_err(|error| format!("failed to join detect_node_installs task: {}", error))
+}
@@ -0,0 +1,92 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PRESETS_DIR: &str = "presets";
+
+#[derive(Serialize)]
+pub struct PresetSummary {
+    id: String,
+    name: String,
+    path: String,
+}
+
+fn presets_dir() -> PathBuf {
+    crate::project_root().join(PRESETS_DIR)
+}
+
+fn is_json_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|value| value.to_str())
+            .map(|value| value.eq_ignore_ascii_case("json"))
+            .unwrap_or(false)
+}
+
+fn read_preset(path: &Path) -> Result<Value, String> {
+    let raw = crate::load_text_file(path, None, None)?;
+    serde_json::from_str(&raw)
+        .map_err(|error| format!("failed to parse {}: {}", path.display(), error))
+}
+
+fn summarize(path: &Path, root: &Path) -> Option<PresetSummary> {
+    let id = path.file_stem()?.to_string_lossy().to_string();
+    let document = match read_preset(path) {
+        Ok(document) => document,
+        Err(error) => {
+            log::warn!("skipping preset {}: {}", path.display(), error);
+            return None;
+        }
+    };
+
+    let name = document
+        .get("name")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| id.clone());
+    Some(PresetSummary {
+        id,
+        name,
+        path: crate::to_display_path(path, root),
+    })
+}
+
+#[tauri::command]
+pub fn list_presets() -> Result<Vec<PresetSummary>, String> {
+    let dir = presets_dir();
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let root = crate::project_root();
+    let entries = fs::read_dir(&dir)
+        .map_err(|error| format!("failed to read {}: {}", dir.display(), error))?;
+    let mut presets = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| is_json_file(path))
+        .filter_map(|path| summarize(&path, &root))
+        .collect::<Vec<PresetSummary>>();
+    presets.sort_by(|left, right| {
+        left.name
+            .cmp(&right.name)
+            .then_with(|| left.id.cmp(&right.id))
+    });
+    Ok(presets)
+}
+
+#[tauri::command]
+pub fn load_preset(id: String) -> Result<Value, String> {
+    let id = id.trim();
+    let is_plain_name = !id.is_empty()
+        && Path::new(id)
+            .file_name()
+            .map(|name| name == id)
+            .unwrap_or(false);
+    if !is_plain_name {
+        return Err(format!("invalid preset id: {}", id));
+    }
+
+    read_preset(&presets_dir().join(format!("{}.json", id)))
+}
@@ -0,0 +1,71 @@
+use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::window_state::MAIN_WINDOW_LABEL;
+
+// Preview windows share the app's IPC and asset access, so they only load the app's own pages.
+fn parse_preview_url(url: &str) -> Result<WebviewUrl, String> {
+    let trimmed = url.trim();
+    if trimmed.contains("://") || trimmed.starts_with("//") {
+        return Err(format!("preview windows only load app pages: {}", trimmed));
+    }
+
+    Ok(WebviewUrl::App(trimmed.trim_start_matches('/').into()))
+}
+
+// Window creation must not run on the main thread on Windows, so these commands stay async.
+#[tauri::command]
+pub async fn open_preview_window(
+    app: tauri::AppHandle,
+    label: String,
+    url: String,
+    always_on_top: Option<bool>,
+) -> Result<(), String> {
+    let label = label.trim().to_string();
+    if label.is_empty() || label == MAIN_WINDOW_LABEL {
+        return Err(format!("invalid preview window label: {:?}", label));
+    }
+
+    if let Some(existing) = app.get_webview_window(&label) {
+        if let Some(always_on_top) = always_on_top {
+            existing
+                .set_always_on_top(always_on_top)
+                .map_err(|error| format!("failed to update preview window: {}", error))?;
+        }
+        existing
+            .unminimize()
+            .and_then(|_| existing.show())
+            .and_then(|_| existing.set_focus())
+            .map_err(|error| format!("failed to focus preview window: {}", error))?;
+        return Ok(());
+    }
+
+    let window = WebviewWindowBuilder::new(&app, &label, parse_preview_url(&url)?)
+        .title("Preview")
+        .decorations(false)
+        .always_on_top(always_on_top.unwrap_or(false))
+        .inner_size(800.0, 600.0)
+        .build()
+        .map_err(|error| format!("failed to open preview window: {}", error))?;
+
+    #[cfg(target_os = "macos")]
+    crate::disable_swipe_navigation(&window);
+    #[cfg(not(target_os = "macos"))]
+    let _ = window;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn close_preview_window(app: tauri::AppHandle, label: String) -> Result<(), String> {
+    let label = label.trim();
+    if label == MAIN_WINDOW_LABEL {
+        return Err("refusing to close the main window".to_string());
+    }
+
+    match app.get_webview_window(label) {
+        Some(window) => window
+            .destroy()
+            .map_err(|error| format!("failed to close preview window: {}", error)),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,140 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BACKUP_DIR: &str = ".backups";
+const MAX_PROJECT_BACKUPS: usize = 10;
+const PROJECT_FILE_SUFFIXES: [&str; 2] = [".storeshot.json", ".dma.json"];
+
+#[derive(Serialize)]
+pub struct ProjectBackup {
+    id: String,
+    created_ms: u64,
+    bytes: u64,
+}
+
+fn is_project_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| {
+            let name = name.to_ascii_lowercase();
+            PROJECT_FILE_SUFFIXES
+                .iter()
+                .any(|suffix| name.len() > suffix.len() && name.ends_with(suffix))
+        })
+        .unwrap_or(false)
+}
+
+fn backup_location(project: &Path) -> Result<(PathBuf, String), String> {
+    let invalid = || format!("invalid project path: {}", project.display());
+    let directory = project.parent().ok_or_else(invalid)?.join(BACKUP_DIR);
+    let file_name = project
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(invalid)?;
+    Ok((directory, file_name))
+}
+
+// Backups are named `<created_ms>-<project file name>`, newest first.
+fn read_backups(directory: &Path, file_name: &str) -> Vec<ProjectBackup> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    let suffix = format!("-{}", file_name);
+
+    let mut backups = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let id = entry.file_name().to_string_lossy().to_string();
+            let created_ms = id.strip_suffix(&suffix)?.parse::<u64>().ok()?;
+            let bytes = entry.metadata().ok()?.len();
+            Some(ProjectBackup {
+                id,
+                created_ms,
+                bytes,
+            })
+        })
+        .collect::<Vec<ProjectBackup>>();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created_ms));
+    backups
+}
+
+pub(crate) fn backup_before_write(project: &Path) -> Result<(), String> {
+    if !is_project_file(project) || !project.is_file() {
+        return Ok(());
+    }
+    let previous = fs::read(project)
+        .map_err(|error| format!("failed to read {}: {}", project.display(), error))?;
+    let (directory, file_name) = backup_location(project)?;
+    let existing = read_backups(&directory, &file_name);
+    // Repeated saves without edits would otherwise push older history out of the window.
+    if let Some(latest) = existing.first() {
+        if fs::read(directory.join(&latest.id)).ok().as_deref() == Some(previous.as_slice()) {
+            return Ok(());
+        }
+    }
+
+    fs::create_dir_all(&directory)
+        .map_err(|error| format!("failed to create {}: {}", directory.display(), error))?;
+    let mut created_ms = crate::now_ms();
+    while directory
+        .join(format!("{}-{}", created_ms, file_name))
+        .exists()
+    {
+        created_ms += 1;
+    }
+    crate::write_atomically(
+        &directory.join(format!("{}-{}", created_ms, file_name)),
+        &previous,
+    )?;
+
+    for stale in read_backups(&directory, &file_name)
+        .into_iter()
+        .skip(MAX_PROJECT_BACKUPS)
+    {
+        let stale_path = directory.join(&stale.id);
+        if let Err(error) = fs::remove_file(&stale_path) {
+            log::warn!(
+                "failed to prune project backup {}: {}",
+                stale_path.display(),
+                error
+            );
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_project_backups(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<Vec<ProjectBackup>, String> {
+    let project = crate::resolve_scoped_path(&app, path.trim())?;
+    let (directory, file_name) = backup_location(&project)?;
+    Ok(read_backups(&directory, &file_name))
+}
+
+#[tauri::command]
+pub fn restore_project_backup(
+    app: tauri::AppHandle,
+    project_watch: tauri::State<'_, crate::project_watch::ProjectWatch>,
+    path: String,
+    backup_id: String,
+) -> Result<(), String> {
+    let project = crate::resolve_scoped_path(&app, path.trim())?;
+    let (directory, file_name) = backup_location(&project)?;
+    let backup_id = backup_id.trim();
+    if !read_backups(&directory, &file_name)
+        .iter()
+        .any(|backup| backup.id == backup_id)
+    {
+        return Err(format!("unknown project backup: {}", backup_id));
+    }
+
+    let content = fs::read(directory.join(backup_id))
+        .map_err(|error| format!("failed to read backup {}: {}", backup_id, error))?;
+    // Restoring is itself a write, so the version being replaced stays recoverable.
+    backup_before_write(&project)?;
+    project_watch.note_own_write(&project);
+    crate::write_atomically(&project, &content)
+}
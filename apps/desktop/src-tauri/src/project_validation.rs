@@ -0,0 +1,93 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+const SUPPORTED_SCHEMA_VERSIONS: [u64; 1] = [1];
+const REQUIRED_OBJECT_KEYS: [&str; 2] = ["project", "template"];
+const OPTIONAL_OBJECT_KEYS: [&str; 2] = ["copy", "pipelines"];
+const PROJECT_ARRAY_KEYS: [&str; 4] = ["platforms", "locales", "devices", "slots"];
+
+#[derive(Serialize)]
+pub struct ProjectValidation {
+    valid: bool,
+    version: Option<u64>,
+    errors: Vec<String>,
+}
+
+fn validate_schema_version(root: &Map<String, Value>, errors: &mut Vec<String>) -> Option<u64> {
+    let Some(raw) = root.get("schemaVersion") else {
+        errors.push("missing required key `schemaVersion`".to_string());
+        return None;
+    };
+
+    let Some(version) = raw.as_u64() else {
+        errors.push(format!(
+            "`schemaVersion` must be a positive integer, got {}",
+            raw
+        ));
+        return None;
+    };
+
+    if !SUPPORTED_SCHEMA_VERSIONS.contains(&version) {
+        errors.push(format!("unsupported schema version {}", version));
+    }
+    Some(version)
+}
+
+fn validate_document(document: &Value) -> ProjectValidation {
+    let mut errors = Vec::new();
+    let Some(root) = document.as_object() else {
+        return ProjectValidation {
+            valid: false,
+            version: None,
+            errors: vec!["project file must contain a JSON object".to_string()],
+        };
+    };
+
+    let version = validate_schema_version(root, &mut errors);
+
+    for key in REQUIRED_OBJECT_KEYS {
+        match root.get(key) {
+            Some(Value::Object(_)) => {}
+            Some(_) => errors.push(format!("`{}` must be an object", key)),
+            None => errors.push(format!("missing required key `{}`", key)),
+        }
+    }
+
+    for key in OPTIONAL_OBJECT_KEYS {
+        if matches!(root.get(key), Some(value) if !value.is_object()) {
+            errors.push(format!("`{}` must be an object", key));
+        }
+    }
+
+    if let Some(project) = root.get("project").and_then(Value::as_object) {
+        for key in PROJECT_ARRAY_KEYS {
+            if matches!(project.get(key), Some(value) if !value.is_array()) {
+                errors.push(format!("`project.{}` must be an array", key));
+            }
+        }
+    }
+
+    ProjectValidation {
+        valid: errors.is_empty(),
+        version,
+        errors,
+    }
+}
+
+#[tauri::command]
+pub fn validate_project_file(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<ProjectValidation, String> {
+    let resolved = crate::resolve_scoped_path(&app, &path)?;
+    let raw = crate::load_text_file(&resolved, None, None)?;
+
+    match serde_json::from_str::<Value>(&raw) {
+        Ok(document) => Ok(validate_document(&document)),
+        Err(error) => Ok(ProjectValidation {
+            valid: false,
+            version: None,
+            errors: vec![format!("invalid JSON: {}", error)],
+        }),
+    }
+}
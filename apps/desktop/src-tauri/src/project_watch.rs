@@ -0,0 +1,162 @@
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use tauri::Emitter;
+
+const OWN_WRITE_SUPPRESSION: Duration = Duration::from_millis(1500);
+
+struct ActiveProjectWatch {
+    path: PathBuf,
+    _watcher: RecommendedWatcher,
+}
+
+#[derive(Default)]
+pub struct ProjectWatch {
+    active: Mutex<Option<ActiveProjectWatch>>,
+    own_writes: Arc<Mutex<HashMap<PathBuf, Instant>>>,
+}
+
+impl ProjectWatch {
+    pub fn note_own_write(&self, path: &Path) {
+        self.own_writes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(path.to_path_buf(), Instant::now());
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct ExternalChange {
+    path: String,
+    mtime_ms: Option<u64>,
+    hash: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct WatchedProjectFile {
+    mtime_ms: Option<u64>,
+    hash: Option<String>,
+}
+
+fn modified_ms(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    modified
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_millis() as u64)
+}
+
+fn content_hash(path: &Path) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    Some(blake3::hash(&bytes).to_hex().to_string())
+}
+
+fn recently_written(own_writes: &Mutex<HashMap<PathBuf, Instant>>, path: &Path) -> bool {
+    let mut writes = own_writes
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    writes.retain(|_, written| written.elapsed() < OWN_WRITE_SUPPRESSION);
+    writes.contains_key(path)
+}
+
+#[tauri::command]
+pub fn begin_watch_project(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, ProjectWatch>,
+    path: String,
+) -> Result<WatchedProjectFile, String> {
+    let resolved = crate::resolve_scoped_path(&app, path.trim())?;
+    let directory = resolved
+        .parent()
+        .map(PathBuf::from)
+        .ok_or_else(|| format!("invalid project path: {}", resolved.display()))?;
+    let file_name = resolved
+        .file_name()
+        .map(|name| name.to_os_string())
+        .ok_or_else(|| format!("invalid project path: {}", resolved.display()))?;
+
+    let initial_mtime = modified_ms(&resolved);
+    let initial_hash = content_hash(&resolved);
+    let last_mtime = Arc::new(Mutex::new(initial_mtime));
+    let last_hash = Arc::new(Mutex::new(initial_hash.clone()));
+    let own_writes = state.own_writes.clone();
+    let watched = resolved.clone();
+    let display_path = crate::to_display_path(&resolved, &crate::project_root());
+
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        let Ok(event) = result else {
+            return;
+        };
+        let touches_file = event
+            .paths
+            .iter()
+            .any(|changed| changed.file_name() == Some(file_name.as_os_str()));
+        if !touches_file || matches!(event.kind, notify::EventKind::Access(_)) {
+            return;
+        }
+
+        let mtime_ms = modified_ms(&watched);
+        let mut last = last_mtime
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if *last == mtime_ms {
+            return;
+        }
+        *last = mtime_ms;
+        drop(last);
+
+        // Touches and saves without edits change the mtime but not the content.
+        let hash = content_hash(&watched);
+        let mut known = last_hash
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if *known == hash {
+            return;
+        }
+        *known = hash.clone();
+        drop(known);
+
+        if recently_written(&own_writes, &watched) {
+            return;
+        }
+        let payload = ExternalChange {
+            path: display_path.clone(),
+            mtime_ms,
+            hash,
+        };
+        if let Err(error) = app.emit("project://changed-on-disk", payload) {
+            log::warn!("failed to emit project change event: {}", error);
+        }
+    })
+    .map_err(|error| format!("failed to create project watcher: {}", error))?;
+    // Editors often save by replacing the file, so watch the containing directory.
+    watcher
+        .watch(&directory, RecursiveMode::NonRecursive)
+        .map_err(|error| format!("failed to watch {}: {}", directory.display(), error))?;
+
+    *state
+        .active
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(ActiveProjectWatch {
+        path: resolved,
+        _watcher: watcher,
+    });
+    Ok(WatchedProjectFile {
+        mtime_ms: initial_mtime,
+        hash: initial_hash,
+    })
+}
+
+#[tauri::command]
+pub fn stop_watch_project(state: tauri::State<'_, ProjectWatch>) -> Option<String> {
+    state
+        .active
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take()
+        .map(|active| active.path.to_string_lossy().replace('\\', "/"))
+}
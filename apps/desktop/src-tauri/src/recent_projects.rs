@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::config_store;
+
+const RECENT_PROJECTS_FILE: &str = "recent-projects.json";
+const MAX_RECENT_PROJECTS: usize = 20;
+
+#[derive(Clone, Serialize, Deserialize)]
+struct StoredRecentProject {
+    path: String,
+    last_opened_ms: u64,
+}
+
+#[derive(Serialize)]
+pub struct RecentProject {
+    path: String,
+    exists: bool,
+    last_opened_ms: u64,
+}
+
+fn load_entries(app: &tauri::AppHandle) -> Vec<StoredRecentProject> {
+    config_store::load_json(app, RECENT_PROJECTS_FILE).unwrap_or_default()
+}
+
+pub(crate) fn recent_project_dirs<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Vec<PathBuf> {
+    config_store::load_json::<_, Vec<StoredRecentProject>>(app, RECENT_PROJECTS_FILE)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| PathBuf::from(entry.path).parent()?.canonicalize().ok())
+        .collect()
+}
+
+#[tauri::command]
+pub fn add_recent_project(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    let resolved = crate::resolve_project_path(path.trim());
+    let normalized = resolved.to_string_lossy().replace('\\', "/");
+
+    let mut entries = load_entries(&app);
+    entries.retain(|entry| entry.path != normalized);
+    entries.insert(
+        0,
+        StoredRecentProject {
+            path: normalized,
+            last_opened_ms: crate::now_ms(),
+        },
+    );
+    entries.truncate(MAX_RECENT_PROJECTS);
+
+    config_store::save_json(&app, RECENT_PROJECTS_FILE, &entries)
+}
+
+#[tauri::command]
+pub fn list_recent_projects(app: tauri::AppHandle) -> Vec<RecentProject> {
+    load_entries(&app)
+        .into_iter()
+        .map(|entry| RecentProject {
+            exists: PathBuf::from(&entry.path).is_file(),
+            path: entry.path,
+            last_opened_ms: entry.last_opened_ms,
+        })
+        .collect()
+}
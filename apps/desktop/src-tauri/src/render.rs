@@ -0,0 +1,366 @@
+use ab_glyph::FontRef;
+use image::imageops::FilterType;
+use image::{DynamicImage, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::Manager;
+
+use crate::fonts::FontCache;
+use crate::images::parse_hex_color;
+
+const DEFAULT_GRADIENT_ANGLE: f32 = 180.0;
+const DEFAULT_LINE_HEIGHT: f32 = 1.2;
+const DEFAULT_CAPTION_COLOR: &str = "#ffffff";
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum NativeBackground {
+    Solid {
+        color: String,
+    },
+    Gradient {
+        from: String,
+        to: String,
+        angle: Option<f32>,
+    },
+    Image {
+        path: String,
+    },
+}
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ShotFit {
+    #[default]
+    Cover,
+    Contain,
+}
+
+#[derive(Deserialize)]
+struct NativeShot {
+    path: String,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    #[serde(default)]
+    fit: ShotFit,
+    corner_radius: Option<u32>,
+}
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum CaptionAlign {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+#[derive(Deserialize)]
+struct NativeCaption {
+    text: String,
+    font: String,
+    size: u32,
+    color: Option<String>,
+    x: u32,
+    y: u32,
+    width: u32,
+    #[serde(default)]
+    align: CaptionAlign,
+    line_height: Option<f32>,
+}
+
+#[derive(Deserialize)]
+pub struct NativeComposition {
+    width: u32,
+    height: u32,
+    background: NativeBackground,
+    shot: Option<NativeShot>,
+    #[serde(default)]
+    captions: Vec<NativeCaption>,
+}
+
+#[derive(Serialize)]
+pub struct RenderedImage {
+    path: String,
+    width: u32,
+    height: u32,
+}
+
+fn lerp_channel(from: u8, to: u8, t: f32) -> u8 {
+    (f32::from(from) + (f32::from(to) - f32::from(from)) * t).round() as u8
+}
+
+// Follows CSS linear-gradient angles: 0deg runs bottom to top, 180deg top to bottom.
+fn fill_gradient(canvas: &mut RgbaImage, from: [u8; 3], to: [u8; 3], angle: f32) {
+    let width = canvas.width() as f32;
+    let height = canvas.height() as f32;
+    let radians = angle.to_radians();
+    let (dx, dy) = (radians.sin(), -radians.cos());
+    let length = ((width * dx).abs() + (height * dy).abs()).max(1.0);
+
+    for (x, y, pixel) in canvas.enumerate_pixels_mut() {
+        let px = x as f32 + 0.5 - width / 2.0;
+        let py = y as f32 + 0.5 - height / 2.0;
+        let t = ((px * dx + py * dy) / length + 0.5).clamp(0.0, 1.0);
+        pixel.0 = [
+            lerp_channel(from[0], to[0], t),
+            lerp_channel(from[1], to[1], t),
+            lerp_channel(from[2], to[2], t),
+            255,
+        ];
+    }
+}
+
+// Image sources are resolved and scope-checked by render_native before this runs.
+fn draw_background(
+    canvas: &mut RgbaImage,
+    background: &NativeBackground,
+    image_source: Option<&Path>,
+) -> Result<(), String> {
+    match background {
+        NativeBackground::Solid { color } => {
+            let [red, green, blue] = parse_hex_color(color)?;
+            for pixel in canvas.pixels_mut() {
+                pixel.0 = [red, green, blue, 255];
+            }
+        }
+        NativeBackground::Gradient { from, to, angle } => fill_gradient(
+            canvas,
+            parse_hex_color(from)?,
+            parse_hex_color(to)?,
+            angle.unwrap_or(DEFAULT_GRADIENT_ANGLE),
+        ),
+        NativeBackground::Image { path } => {
+            let source =
+                image_source.ok_or_else(|| format!("unresolved background image: {}", path))?;
+            let image = image::open(source)
+                .map_err(|error| format!("failed to decode {}: {}", source.display(), error))?
+                .resize_to_fill(canvas.width(), canvas.height(), FilterType::Lanczos3)
+                .to_rgba8();
+            image::imageops::overlay(canvas, &image, 0, 0);
+        }
+    }
+    Ok(())
+}
+
+fn draw_shot(canvas: &mut RgbaImage, shot: &NativeShot, source: &Path) -> Result<(), String> {
+    if shot.width == 0 || shot.height == 0 {
+        return Err("shot width and height must be greater than zero".to_string());
+    }
+
+    let decoded = image::open(source)
+        .map_err(|error| format!("failed to decode {}: {}", source.display(), error))?;
+    let mut screen = match shot.fit {
+        ShotFit::Cover => decoded.resize_to_fill(shot.width, shot.height, FilterType::Lanczos3),
+        ShotFit::Contain => decoded.resize(shot.width, shot.height, FilterType::Lanczos3),
+    }
+    .to_rgba8();
+    if let Some(radius) = shot.corner_radius {
+        crate::images::apply_corner_radius(&mut screen, radius);
+    }
+
+    let x = i64::from(shot.x) + i64::from((shot.width - screen.width()) / 2);
+    let y = i64::from(shot.y) + i64::from((shot.height - screen.height()) / 2);
+    image::imageops::overlay(canvas, &screen, x, y);
+    Ok(())
+}
+
+// `measure` returns the rendered width of a line in pixels.
+fn wrap_caption(text: &str, max_width: u32, measure: impl Fn(&str) -> f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut current = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if current.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", current, word)
+            };
+            if !current.is_empty() && measure(&candidate) > max_width as f32 {
+                lines.push(std::mem::replace(&mut current, word.to_string()));
+            } else {
+                current = candidate;
+            }
+        }
+        lines.push(current);
+    }
+    lines
+}
+
+fn draw_caption(
+    canvas: &mut RgbaImage,
+    cache: &FontCache,
+    caption: &NativeCaption,
+) -> Result<(), String> {
+    if caption.size == 0 {
+        return Err("caption size must be greater than zero".to_string());
+    }
+
+    let color = parse_hex_color(caption.color.as_deref().unwrap_or(DEFAULT_CAPTION_COLOR))?;
+    let (data, index) = crate::fonts::load_family_data(cache, &caption.font)?;
+    let font = FontRef::try_from_slice_and_index(&data, index)
+        .map_err(|error| format!("failed to parse font {}: {}", caption.font, error))?;
+
+    let line_height = caption.size as f32 * caption.line_height.unwrap_or(DEFAULT_LINE_HEIGHT);
+    let lines = wrap_caption(&caption.text, caption.width, |line| {
+        crate::fonts::measure_text(&font, line, caption.size)
+    });
+    for (line_index, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+
+        let rendered = crate::fonts::rasterize_text(&font, line, caption.size, color)?;
+        let free = i64::from(caption.width) - i64::from(rendered.width());
+        let x = i64::from(caption.x)
+            + match caption.align {
+                CaptionAlign::Left => 0,
+                CaptionAlign::Center => free / 2,
+                CaptionAlign::Right => free,
+            };
+        let y = i64::from(caption.y) + (line_index as f32 * line_height).round() as i64;
+        image::imageops::overlay(canvas, &rendered, x, y);
+    }
+    Ok(())
+}
+
+// Covers the screenshot + background + caption layouts without needing node; templates with other
+// element kinds still go through run_pipeline.
+#[tauri::command]
+pub async fn render_native(
+    app: tauri::AppHandle,
+    composition: NativeComposition,
+    out: String,
+) -> Result<RenderedImage, String> {
+    if composition.width == 0 || composition.height == 0 {
+        return Err("composition width and height must be greater than zero".to_string());
+    }
+    let destination = crate::resolve_scoped_path(&app, out.trim())?;
+    let background_image = match &composition.background {
+        NativeBackground::Image { path } => Some(crate::resolve_scoped_path(&app, path)?),
+        _ => None,
+    };
+    let shot_source = composition
+        .shot
+        .as_ref()
+        .map(|shot| crate::resolve_scoped_path(&app, &shot.path))
+        .transpose()?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut canvas = RgbaImage::new(composition.width, composition.height);
+        draw_background(
+            &mut canvas,
+            &composition.background,
+            background_image.as_deref(),
+        )?;
+        if let (Some(shot), Some(source)) = (&composition.shot, &shot_source) {
+            draw_shot(&mut canvas, shot, source)?;
+        }
+        let cache = app.state::<FontCache>();
+        for caption in &composition.captions {
+            draw_caption(&mut canvas, &cache, caption)?;
+        }
+
+        let format = destination
+            .extension()
+            .and_then(|value| value.to_str())
+            .unwrap_or("png");
+        let encoded = crate::images::encode_image(&DynamicImage::ImageRgba8(canvas), format, None)?;
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+        }
+        crate::write_atomically(&destination, &encoded)?;
+
+        Ok(RenderedImage {
+            path: destination.to_string_lossy().replace('\\', "/"),
+            width: composition.width,
+            height: composition.height,
+        })
+    })
+    .await
+    .map_err(|error| format!("failed to join render_native task: {}", error))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: [u8; 3] = [0, 0, 0];
+    const WHITE: [u8; 3] = [255, 255, 255];
+
+    fn gradient(angle: f32) -> RgbaImage {
+        let mut canvas = RgbaImage::new(4, 4);
+        fill_gradient(&mut canvas, BLACK, WHITE, angle);
+        canvas
+    }
+
+    fn level(canvas: &RgbaImage, x: u32, y: u32) -> u8 {
+        canvas.get_pixel(x, y).0[0]
+    }
+
+    #[test]
+    fn gradient_angles_follow_css_directions() {
+        let down = gradient(180.0);
+        assert!(level(&down, 0, 0) < level(&down, 0, 3));
+        assert_eq!(level(&down, 0, 1), level(&down, 3, 1));
+
+        let up = gradient(0.0);
+        assert!(level(&up, 0, 0) > level(&up, 0, 3));
+        assert_eq!(level(&up, 1, 2), level(&down, 1, 1));
+
+        let right = gradient(90.0);
+        assert!(level(&right, 0, 0) < level(&right, 3, 0));
+        assert_eq!(level(&right, 1, 0), level(&right, 1, 3));
+
+        let left = gradient(270.0);
+        assert!(level(&left, 0, 0) > level(&left, 3, 0));
+    }
+
+    #[test]
+    fn diagonal_gradients_run_corner_to_corner_and_wrap_around() {
+        let toward_top_right = gradient(45.0);
+        let darkest = level(&toward_top_right, 0, 3);
+        let brightest = level(&toward_top_right, 3, 0);
+        assert!(darkest < level(&toward_top_right, 0, 0));
+        assert!(brightest > level(&toward_top_right, 3, 3));
+        assert_eq!(
+            level(&toward_top_right, 0, 0),
+            level(&toward_top_right, 3, 3)
+        );
+
+        assert_eq!(gradient(540.0), gradient(180.0));
+        assert_eq!(gradient(-90.0), gradient(270.0));
+        assert!(gradient(180.0).pixels().all(|pixel| pixel.0[3] == 255));
+    }
+
+    #[test]
+    fn captions_wrap_on_word_boundaries() {
+        let measure = |line: &str| line.chars().count() as f32 * 10.0;
+        assert_eq!(
+            wrap_caption("Ship screenshots faster than ever", 150, measure),
+            vec!["Ship", "screenshots", "faster than", "ever"]
+        );
+        assert_eq!(
+            wrap_caption("Fits on one line", 160, measure),
+            vec!["Fits on one line"]
+        );
+    }
+
+    #[test]
+    fn captions_keep_explicit_breaks_and_overlong_words() {
+        let measure = |line: &str| line.chars().count() as f32 * 10.0;
+        assert_eq!(
+            wrap_caption("First\n\nThird  line", 200, measure),
+            vec!["First", "", "Third line"]
+        );
+        assert_eq!(
+            wrap_caption("Supercalifragilistic words", 50, measure),
+            vec!["Supercalifragilistic", "words"]
+        );
+        assert!(wrap_caption("", 100, measure).is_empty());
+    }
+}
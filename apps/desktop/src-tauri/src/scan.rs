@@ -0,0 +1,140 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
+
+const SCAN_BATCH_SIZE: usize = 500;
+
+#[derive(Default)]
+pub struct ScanRegistry {
+    next_id: AtomicU64,
+    running: Mutex<HashMap<u64, Arc<AtomicBool>>>,
+}
+
+#[derive(Clone, Serialize)]
+struct ScanBatch {
+    scan_id: u64,
+    paths: Vec<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct ScanDone {
+    scan_id: u64,
+    total: usize,
+    cancelled: bool,
+}
+
+fn sorted_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut entries = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect::<Vec<PathBuf>>(),
+        Err(error) => {
+            log::warn!("failed to read {}: {}", dir.display(), error);
+            return Vec::new();
+        }
+    };
+    entries.sort();
+    entries
+}
+
+fn emit_batch(app: &tauri::AppHandle, scan_id: u64, paths: Vec<String>) {
+    if let Err(error) = app.emit("scan://batch", ScanBatch { scan_id, paths }) {
+        log::warn!("failed to emit scan batch event: {}", error);
+    }
+}
+
+fn walk(app: &tauri::AppHandle, scan_id: u64, start: PathBuf, cancelled: &AtomicBool) -> usize {
+    let root = crate::project_root();
+    let mut pending = vec![start];
+    let mut batch = Vec::with_capacity(SCAN_BATCH_SIZE);
+    let mut total = 0;
+
+    while let Some(dir) = pending.pop() {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let entries = sorted_entries(&dir);
+        // Symlinked directories are skipped so a link cycle cannot stall the walk; subdirectories are
+        // pushed in reverse so they are visited in sorted order.
+        pending.extend(
+            entries
+                .iter()
+                .rev()
+                .filter(|path| path.is_dir() && !path.is_symlink())
+                .cloned(),
+        );
+        for path in entries.iter().filter(|path| crate::is_png_file(path)) {
+            batch.push(crate::to_display_path(path, &root));
+            if batch.len() == SCAN_BATCH_SIZE {
+                total += batch.len();
+                emit_batch(app, scan_id, std::mem::take(&mut batch));
+            }
+        }
+    }
+
+    if !batch.is_empty() {
+        total += batch.len();
+        emit_batch(app, scan_id, batch);
+    }
+    total
+}
+
+#[tauri::command]
+pub fn scan_png_files(
+    app: tauri::AppHandle,
+    registry: tauri::State<'_, ScanRegistry>,
+    path: String,
+) -> Result<u64, String> {
+    let resolved = crate::resolve_scoped_path(&app, &path)?;
+    let scan_id = registry.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+    let cancelled = Arc::new(AtomicBool::new(false));
+    registry
+        .running
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(scan_id, cancelled.clone());
+
+    std::thread::spawn(move || {
+        let total = if resolved.is_dir() {
+            walk(&app, scan_id, resolved, &cancelled)
+        } else {
+            0
+        };
+        app.state::<ScanRegistry>()
+            .running
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&scan_id);
+
+        let payload = ScanDone {
+            scan_id,
+            total,
+            cancelled: cancelled.load(Ordering::Relaxed),
+        };
+        if let Err(error) = app.emit("scan://done", payload) {
+            log::warn!("failed to emit scan done event: {}", error);
+        }
+    });
+    Ok(scan_id)
+}
+
+#[tauri::command]
+pub fn cancel_scan(registry: tauri::State<'_, ScanRegistry>, scan_id: u64) -> bool {
+    match registry
+        .running
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&scan_id)
+    {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,88 @@
+use serde::Serialize;
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenPermission {
+    Granted,
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    Denied,
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    NotDetermined,
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::ScreenPermission;
+    use crate::config_store;
+    use serde::{Deserialize, Serialize};
+
+    const SCREEN_PERMISSION_FILE: &str = "screen-permission.json";
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGPreflightScreenCaptureAccess() -> bool;
+        fn CGRequestScreenCaptureAccess() -> bool;
+    }
+
+    #[derive(Default, Serialize, Deserialize)]
+    struct StoredScreenPermission {
+        requested: bool,
+    }
+
+    // CoreGraphics only reports granted or not, so whether the prompt was already shown is kept in
+    // the config dir to tell a denial apart from a first run.
+    pub fn check(app: &tauri::AppHandle) -> ScreenPermission {
+        if unsafe { CGPreflightScreenCaptureAccess() } {
+            return ScreenPermission::Granted;
+        }
+        let stored =
+            config_store::load_json::<_, StoredScreenPermission>(app, SCREEN_PERMISSION_FILE)
+                .unwrap_or_default();
+        if stored.requested {
+            ScreenPermission::Denied
+        } else {
+            ScreenPermission::NotDetermined
+        }
+    }
+
+    pub fn request(app: &tauri::AppHandle) -> ScreenPermission {
+        if unsafe { CGPreflightScreenCaptureAccess() } {
+            return ScreenPermission::Granted;
+        }
+        let granted = unsafe { CGRequestScreenCaptureAccess() };
+        if let Err(error) = config_store::save_json(
+            app,
+            SCREEN_PERMISSION_FILE,
+            &StoredScreenPermission { requested: true },
+        ) {
+            log::warn!("failed to persist screen permission request: {}", error);
+        }
+        if granted {
+            ScreenPermission::Granted
+        } else {
+            ScreenPermission::Denied
+        }
+    }
+}
+
+#[tauri::command]
+pub fn check_screen_permission(app: tauri::AppHandle) -> ScreenPermission {
+    #[cfg(target_os = "macos")]
+    return platform::check(&app);
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app;
+        ScreenPermission::Granted
+    }
+}
+
+#[tauri::command]
+pub fn request_screen_permission(app: tauri::AppHandle) -> ScreenPermission {
+    #[cfg(target_os = "macos")]
+    return platform::request(&app);
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app;
+        ScreenPermission::Granted
+    }
+}
@@ -0,0 +1,127 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+const MAX_SNAPSHOTS: usize = 20;
+
+#[derive(Serialize)]
+pub struct ProjectSnapshot {
+    id: String,
+    created_ms: u64,
+    bytes: u64,
+}
+
+// FNV-1a keeps the directory name stable across builds, unlike `DefaultHasher`.
+fn project_key(resolved: &Path) -> String {
+    let normalized = resolved.to_string_lossy().replace('\\', "/");
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in normalized.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+fn snapshots_root(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|error| format!("failed to resolve app data dir: {}", error))?;
+    Ok(data_dir.join("snapshots"))
+}
+
+fn read_snapshots(directory: &Path, key: &str) -> Vec<ProjectSnapshot> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+
+    let mut snapshots = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let created_ms = name.strip_suffix(".json")?.parse::<u64>().ok()?;
+            let bytes = entry.metadata().ok()?.len();
+            Some(ProjectSnapshot {
+                id: format!("{}/{}", key, created_ms),
+                created_ms,
+                bytes,
+            })
+        })
+        .collect::<Vec<ProjectSnapshot>>();
+    snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.created_ms));
+    snapshots
+}
+
+fn snapshot_file(root: &Path, snapshot_id: &str) -> Result<PathBuf, String> {
+    let invalid = || format!("invalid snapshot id: {}", snapshot_id);
+    let (key, created_ms) = snapshot_id.trim().split_once('/').ok_or_else(invalid)?;
+    if key.len() != 16 || !key.chars().all(|character| character.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let created_ms = created_ms.parse::<u64>().map_err(|_| invalid())?;
+    Ok(root.join(key).join(format!("{}.json", created_ms)))
+}
+
+#[tauri::command]
+pub fn snapshot_project(app: tauri::AppHandle, path: String) -> Result<ProjectSnapshot, String> {
+    let resolved = crate::resolve_scoped_path(&app, path.trim())?;
+    let content = fs::read(&resolved)
+        .map_err(|error| format!("failed to read {}: {}", resolved.display(), error))?;
+
+    let key = project_key(&resolved);
+    let directory = snapshots_root(&app)?.join(&key);
+    fs::create_dir_all(&directory)
+        .map_err(|error| format!("failed to create snapshot dir: {}", error))?;
+
+    let mut created_ms = crate::now_ms();
+    while directory.join(format!("{}.json", created_ms)).exists() {
+        created_ms += 1;
+    }
+    crate::write_atomically(&directory.join(format!("{}.json", created_ms)), &content)?;
+
+    for stale in read_snapshots(&directory, &key)
+        .into_iter()
+        .skip(MAX_SNAPSHOTS)
+    {
+        let stale_path = directory.join(format!("{}.json", stale.created_ms));
+        if let Err(error) = fs::remove_file(&stale_path) {
+            log::warn!(
+                "failed to prune snapshot {}: {}",
+                stale_path.display(),
+                error
+            );
+        }
+    }
+
+    Ok(ProjectSnapshot {
+        id: format!("{}/{}", key, created_ms),
+        created_ms,
+        bytes: content.len() as u64,
+    })
+}
+
+#[tauri::command]
+pub fn list_snapshots(app: tauri::AppHandle, path: String) -> Result<Vec<ProjectSnapshot>, String> {
+    let resolved = crate::resolve_scoped_path(&app, path.trim())?;
+    let key = project_key(&resolved);
+    Ok(read_snapshots(&snapshots_root(&app)?.join(&key), &key))
+}
+
+#[tauri::command]
+pub fn restore_snapshot(
+    app: tauri::AppHandle,
+    snapshot_id: String,
+    dst: String,
+) -> Result<(), String> {
+    let source = snapshot_file(&snapshots_root(&app)?, &snapshot_id)?;
+    let content = fs::read(&source)
+        .map_err(|error| format!("failed to read snapshot {}: {}", snapshot_id, error))?;
+
+    let destination = crate::resolve_scoped_path(&app, dst.trim())?;
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create parent dirs: {}", error))?;
+    }
+    crate::write_atomically(&destination, &content)
+}
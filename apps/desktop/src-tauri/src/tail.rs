@@ -0,0 +1,192 @@
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Mutex;
+use tauri::Emitter;
+
+#[derive(Default)]
+pub struct TailRegistry(Mutex<HashMap<PathBuf, RecommendedWatcher>>);
+
+#[derive(Clone, Serialize)]
+struct TailLine {
+    path: String,
+    line: String,
+}
+
+#[cfg(unix)]
+fn file_identity(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_identity(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+struct TailReader {
+    path: PathBuf,
+    display_path: String,
+    file: Option<fs::File>,
+    identity: Option<(u64, u64)>,
+    position: u64,
+    pending: Vec<u8>,
+    skip_partial_line: bool,
+}
+
+impl TailReader {
+    fn open(path: PathBuf, display_path: String, from_end_bytes: u64) -> Result<Self, String> {
+        let mut file = fs::File::open(&path)
+            .map_err(|error| format!("failed to open {}: {}", path.display(), error))?;
+        let metadata = file
+            .metadata()
+            .map_err(|error| format!("failed to stat {}: {}", path.display(), error))?;
+
+        let position = metadata.len().saturating_sub(from_end_bytes);
+        let mut skip_partial_line = false;
+        if position > 0 {
+            let mut previous = [0u8; 1];
+            file.seek(SeekFrom::Start(position - 1))
+                .and_then(|_| file.read_exact(&mut previous))
+                .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+            skip_partial_line = previous[0] != b'\n';
+        }
+
+        Ok(Self {
+            path,
+            display_path,
+            identity: file_identity(&metadata),
+            file: Some(file),
+            position,
+            pending: Vec::new(),
+            skip_partial_line,
+        })
+    }
+
+    fn reopen(&mut self) {
+        self.file = fs::File::open(&self.path).ok();
+        self.identity = self
+            .file
+            .as_ref()
+            .and_then(|file| file.metadata().ok())
+            .and_then(|metadata| file_identity(&metadata));
+        self.position = 0;
+        self.pending.clear();
+        self.skip_partial_line = false;
+    }
+
+    fn poll(&mut self, app: &tauri::AppHandle) {
+        let Ok(current) = fs::metadata(&self.path) else {
+            self.file = None;
+            return;
+        };
+
+        let replaced = file_identity(&current) != self.identity;
+        if self.file.is_none() || replaced || current.len() < self.position {
+            self.reopen();
+        }
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+
+        let mut appended = Vec::new();
+        let read = file
+            .seek(SeekFrom::Start(self.position))
+            .and_then(|_| file.read_to_end(&mut appended));
+        if let Err(error) = read {
+            log::warn!("failed to read {}: {}", self.path.display(), error);
+            return;
+        }
+        self.position += appended.len() as u64;
+        self.pending.extend_from_slice(&appended);
+
+        while let Some(newline) = self.pending.iter().position(|byte| *byte == b'\n') {
+            let raw = self.pending.drain(..=newline).collect::<Vec<u8>>();
+            if std::mem::take(&mut self.skip_partial_line) {
+                continue;
+            }
+
+            let line = String::from_utf8_lossy(&raw)
+                .trim_end_matches(['\r', '\n'])
+                .to_string();
+            let payload = TailLine {
+                path: self.display_path.clone(),
+                line,
+            };
+            if let Err(error) = app.emit("tail://line", payload) {
+                log::warn!("failed to emit tail line event: {}", error);
+            }
+        }
+    }
+}
+
+fn follow(app: tauri::AppHandle, mut reader: TailReader, events: Receiver<()>) {
+    reader.poll(&app);
+    while events.recv().is_ok() {
+        while events.try_recv().is_ok() {}
+        reader.poll(&app);
+    }
+}
+
+#[tauri::command]
+pub fn tail_file(
+    app: tauri::AppHandle,
+    registry: tauri::State<'_, TailRegistry>,
+    path: String,
+    from_end_bytes: u64,
+) -> Result<(), String> {
+    let resolved = crate::resolve_scoped_path(&app, path.trim())?;
+    let directory = resolved
+        .parent()
+        .map(PathBuf::from)
+        .ok_or_else(|| format!("invalid tail path: {}", resolved.display()))?;
+    let file_name = resolved
+        .file_name()
+        .map(|name| name.to_os_string())
+        .ok_or_else(|| format!("invalid tail path: {}", resolved.display()))?;
+
+    let display_path = crate::to_display_path(&resolved, &crate::project_root());
+    let reader = TailReader::open(resolved.clone(), display_path, from_end_bytes)?;
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        let Ok(event) = result else {
+            return;
+        };
+        let touches_file = event
+            .paths
+            .iter()
+            .any(|changed| changed.file_name() == Some(file_name.as_os_str()));
+        if touches_file && !matches!(event.kind, notify::EventKind::Access(_)) {
+            let _ = sender.send(());
+        }
+    })
+    .map_err(|error| format!("failed to create file watcher: {}", error))?;
+    // Watch the directory so rotation by rename or re-create is still observed.
+    watcher
+        .watch(&directory, RecursiveMode::NonRecursive)
+        .map_err(|error| format!("failed to watch {}: {}", directory.display(), error))?;
+
+    std::thread::spawn(move || follow(app, reader, receiver));
+    registry
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(resolved, watcher);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_tail(registry: tauri::State<'_, TailRegistry>, path: String) -> bool {
+    let resolved = crate::resolve_project_path(path.trim());
+    registry
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(&resolved)
+        .is_some()
+}
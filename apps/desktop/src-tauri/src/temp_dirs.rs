@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::Manager;
+
+use crate::window_state::MAIN_WINDOW_LABEL;
+
+#[derive(Default)]
+pub struct TempDirRegistry {
+    counter: AtomicU64,
+    allocated: Mutex<HashSet<PathBuf>>,
+}
+
+impl TempDirRegistry {
+    fn purge(&self) {
+        let allocated = std::mem::take(
+            &mut *self
+                .allocated
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
+        for directory in allocated {
+            if let Err(error) = fs::remove_dir_all(&directory) {
+                if error.kind() != std::io::ErrorKind::NotFound {
+                    log::warn!(
+                        "failed to remove temp dir {}: {}",
+                        directory.display(),
+                        error
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn sanitize_prefix(prefix: &str) -> String {
+    let cleaned = prefix
+        .trim()
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() || character == '-' || character == '_' {
+                character
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    if cleaned.is_empty() {
+        "scratch".to_string()
+    } else {
+        cleaned
+    }
+}
+
+#[tauri::command]
+pub fn create_temp_dir(
+    registry: tauri::State<'_, TempDirRegistry>,
+    prefix: Option<String>,
+) -> Result<String, String> {
+    let name = format!(
+        "dma-{}-{}-{}",
+        sanitize_prefix(prefix.as_deref().unwrap_or_default()),
+        std::process::id(),
+        registry.counter.fetch_add(1, Ordering::Relaxed)
+    );
+    let directory = std::env::temp_dir().join(name);
+    fs::create_dir_all(&directory)
+        .map_err(|error| format!("failed to create temp dir: {}", error))?;
+    let directory = directory.canonicalize().unwrap_or(directory);
+
+    registry
+        .allocated
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(directory.clone());
+    Ok(directory.to_string_lossy().replace('\\', "/"))
+}
+
+#[tauri::command]
+pub fn cleanup_temp_dir(
+    registry: tauri::State<'_, TempDirRegistry>,
+    path: String,
+) -> Result<(), String> {
+    let requested = PathBuf::from(path.trim());
+    let requested = requested.canonicalize().unwrap_or(requested);
+
+    let removed = registry
+        .allocated
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(&requested);
+    if !removed {
+        return Err(format!(
+            "not a temp dir allocated by create_temp_dir: {}",
+            requested.display()
+        ));
+    }
+
+    match fs::remove_dir_all(&requested) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(format!(
+            "failed to remove temp dir {}: {}",
+            requested.display(),
+            error
+        )),
+    }
+}
+
+pub fn handle_window_event<R: tauri::Runtime>(
+    window: &tauri::Window<R>,
+    event: &tauri::WindowEvent,
+) {
+    if window.label() == MAIN_WINDOW_LABEL && matches!(event, tauri::WindowEvent::Destroyed) {
+        window.state::<TempDirRegistry>().purge();
+    }
+}
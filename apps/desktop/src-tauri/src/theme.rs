@@ -0,0 +1,38 @@
+use serde::Serialize;
+use tauri::Emitter;
+
+#[derive(Clone, Serialize)]
+struct ThemePayload {
+    theme: &'static str,
+}
+
+fn theme_name(theme: tauri::Theme) -> &'static str {
+    match theme {
+        tauri::Theme::Dark => "dark",
+        _ => "light",
+    }
+}
+
+#[tauri::command]
+pub fn get_system_theme<R: tauri::Runtime>(window: tauri::Window<R>) -> Result<String, String> {
+    window
+        .theme()
+        .map(|theme| theme_name(theme).to_string())
+        .map_err(|error| format!("failed to read system theme: {}", error))
+}
+
+pub fn handle_window_event<R: tauri::Runtime>(
+    window: &tauri::Window<R>,
+    event: &tauri::WindowEvent,
+) {
+    let tauri::WindowEvent::ThemeChanged(theme) = event else {
+        return;
+    };
+
+    let payload = ThemePayload {
+        theme: theme_name(*theme),
+    };
+    if let Err(error) = window.emit("theme://changed", payload) {
+        log::warn!("failed to emit theme changed event: {}", error);
+    }
+}
@@ -0,0 +1,243 @@
+use serde::Serialize;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tauri::Manager;
+
+const HASH_SAMPLE_BYTES: u64 = 64 * 1024;
+
+#[derive(Serialize)]
+pub struct Thumbnail {
+    path: String,
+    width: u32,
+    height: u32,
+    cached: bool,
+}
+
+#[derive(Clone, Copy)]
+enum CacheKey {
+    Mtime,
+    Content,
+}
+
+impl CacheKey {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "mtime" => Ok(Self::Mtime),
+            "content" | "hash" => Ok(Self::Content),
+            other => Err(format!("unsupported thumbnail cache key: {}", other)),
+        }
+    }
+}
+
+fn cache_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let cache_dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|error| format!("failed to resolve app cache dir: {}", error))?;
+    Ok(cache_dir.join("thumbnails"))
+}
+
+// Hashing the first and last 64 KiB plus the size catches restored backups whose mtime went
+// backwards without reading whole multi-megabyte renders.
+fn content_fingerprint(path: &Path, hasher: &mut blake3::Hasher) -> Result<(), String> {
+    let read_error =
+        |error: std::io::Error| format!("failed to read {}: {}", path.display(), error);
+    let mut file = fs::File::open(path).map_err(read_error)?;
+    let size = file.metadata().map_err(read_error)?.len();
+    hasher.update(&size.to_le_bytes());
+
+    let mut sample = Vec::new();
+    (&mut file)
+        .take(HASH_SAMPLE_BYTES)
+        .read_to_end(&mut sample)
+        .map_err(read_error)?;
+    hasher.update(&sample);
+
+    let tail_start = size
+        .saturating_sub(HASH_SAMPLE_BYTES)
+        .max(HASH_SAMPLE_BYTES);
+    if tail_start < size {
+        sample.clear();
+        file.seek(SeekFrom::Start(tail_start))
+            .and_then(|_| file.read_to_end(&mut sample))
+            .map_err(read_error)?;
+        hasher.update(&sample);
+    }
+    Ok(())
+}
+
+fn cache_file_name(
+    path: &Path,
+    max_size: u32,
+    assume_srgb: bool,
+    key: CacheKey,
+) -> Result<String, String> {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&max_size.to_le_bytes());
+    hasher.update(&[u8::from(assume_srgb)]);
+    match key {
+        CacheKey::Mtime => {
+            let modified = fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .map_err(|error| format!("failed to stat {}: {}", path.display(), error))?;
+            let modified_ns = modified
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_nanos())
+                .unwrap_or(0);
+            hasher.update(b"mtime");
+            hasher.update(path.to_string_lossy().as_bytes());
+            hasher.update(&modified_ns.to_le_bytes());
+        }
+        CacheKey::Content => {
+            hasher.update(b"content");
+            content_fingerprint(path, &mut hasher)?;
+        }
+    }
+    Ok(format!("{}.png", hasher.finalize().to_hex()))
+}
+
+#[tauri::command]
+pub async fn generate_thumbnail(
+    app: tauri::AppHandle,
+    path: String,
+    max_size: u32,
+    cache_key: Option<String>,
+    assume_srgb: Option<bool>,
+) -> Result<Thumbnail, String> {
+    if max_size == 0 {
+        return Err("max_size must be greater than zero".to_string());
+    }
+    let key = cache_key
+        .as_deref()
+        .map(CacheKey::parse)
+        .transpose()?
+        .unwrap_or(CacheKey::Mtime);
+    let source = crate::resolve_scoped_path(&app, path.trim())?;
+    let directory = cache_dir(&app)?;
+    let assume_srgb = assume_srgb.unwrap_or(false);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let target = directory.join(cache_file_name(&source, max_size, assume_srgb, key)?);
+        let display_path = target.to_string_lossy().replace('\\', "/");
+        if let Ok((width, height)) = image::image_dimensions(&target) {
+            return Ok(Thumbnail {
+                path: display_path,
+                width,
+                height,
+                cached: true,
+            });
+        }
+
+        let decoded = crate::images::open_profiled(&source, assume_srgb)?;
+        let thumbnail = decoded.image.thumbnail(max_size, max_size);
+        let encoded = crate::images::encode_with_profile(
+            &thumbnail,
+            image::ImageFormat::Png,
+            decoded.icc_profile.as_deref(),
+        )?;
+
+        fs::create_dir_all(&directory)
+            .map_err(|error| format!("failed to create thumbnail cache dir: {}", error))?;
+        crate::write_atomically(&target, &encoded)?;
+        Ok(Thumbnail {
+            path: display_path,
+            width: thumbnail.width(),
+            height: thumbnail.height(),
+            cached: false,
+        })
+    })
+    .await
+    .map_err(|error| format!("failed to join generate_thumbnail task: {}", error))?
+}
+
+#[tauri::command]
+pub fn clear_thumbnail_cache(app: tauri::AppHandle) -> Result<(), String> {
+    let directory = cache_dir(&app)?;
+    match fs::remove_dir_all(&directory) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(format!(
+            "failed to clear {}: {}",
+            directory.display(),
+            error
+        )),
+    }
+}
+
+#[tauri::command]
+pub fn thumbnail_cache_dir(app: tauri::AppHandle) -> Result<String, String> {
+    cache_dir(&app).map(|directory| directory.to_string_lossy().replace('\\', "/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dma-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn fingerprint(path: &Path) -> blake3::Hash {
+        let mut hasher = blake3::Hasher::new();
+        content_fingerprint(path, &mut hasher).unwrap();
+        hasher.finalize()
+    }
+
+    fn whole_file_fingerprint(content: &[u8]) -> blake3::Hash {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&(content.len() as u64).to_le_bytes());
+        hasher.update(content);
+        hasher.finalize()
+    }
+
+    fn patterned(len: usize) -> Vec<u8> {
+        (0..len).map(|index| (index % 251) as u8).collect()
+    }
+
+    #[test]
+    fn files_up_to_two_samples_are_hashed_once_without_overlap() {
+        let dir = scratch_dir("fingerprint-small");
+        let sample = HASH_SAMPLE_BYTES as usize;
+        for len in [0, 10, sample, sample + 1, sample + 36 * 1024, 2 * sample] {
+            let path = dir.join(format!("{}.bin", len));
+            let content = patterned(len);
+            fs::write(&path, &content).unwrap();
+            assert_eq!(
+                fingerprint(&path),
+                whole_file_fingerprint(&content),
+                "{} bytes",
+                len
+            );
+        }
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn large_files_sample_only_the_head_tail_and_size() {
+        let dir = scratch_dir("fingerprint-large");
+        let path = dir.join("render.png");
+        let mut content = patterned(1024 * 1024);
+        fs::write(&path, &content).unwrap();
+        let original = fingerprint(&path);
+
+        content[512 * 1024] ^= 0xff;
+        fs::write(&path, &content).unwrap();
+        assert_eq!(fingerprint(&path), original);
+
+        for index in [0, content.len() - 1] {
+            let mut changed = content.clone();
+            changed[index] ^= 0xff;
+            fs::write(&path, &changed).unwrap();
+            assert_ne!(fingerprint(&path), original, "byte {}", index);
+        }
+
+        content.push(0);
+        fs::write(&path, &content).unwrap();
+        assert_ne!(fingerprint(&path), original);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,334 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Manager, PhysicalPosition, PhysicalSize};
+
+use crate::config_store;
+
+pub const MAIN_WINDOW_LABEL: &str = "main";
+
+const WINDOW_STATE_FILE: &str = "window-state.json";
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+const MIN_VISIBLE_EDGE: i64 = 64;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct WindowState {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    maximized: bool,
+}
+
+#[derive(Default)]
+pub struct WindowStateTracker {
+    pending: AtomicBool,
+    saver: Mutex<Option<mpsc::Sender<()>>>,
+    last_normal: Mutex<Option<WindowState>>,
+}
+
+#[derive(Clone, Copy)]
+struct MonitorBounds {
+    x: i64,
+    y: i64,
+    width: i64,
+    height: i64,
+}
+
+impl MonitorBounds {
+    fn from_monitor(monitor: &tauri::Monitor) -> Self {
+        let area = monitor.work_area();
+        Self {
+            x: i64::from(area.position.x),
+            y: i64::from(area.position.y),
+            width: i64::from(area.size.width),
+            height: i64::from(area.size.height),
+        }
+    }
+
+    fn overlap(&self, state: &WindowState) -> (i64, i64) {
+        let left = self.x.max(i64::from(state.x));
+        let top = self.y.max(i64::from(state.y));
+        let right = (self.x + self.width).min(i64::from(state.x) + i64::from(state.width));
+        let bottom = (self.y + self.height).min(i64::from(state.y) + i64::from(state.height));
+        ((right - left).max(0), (bottom - top).max(0))
+    }
+}
+
+fn clamp_to_monitors(
+    mut state: WindowState,
+    monitors: &[MonitorBounds],
+    primary: Option<MonitorBounds>,
+) -> WindowState {
+    let Some(fallback) = primary.or_else(|| monitors.first().copied()) else {
+        return state;
+    };
+
+    let visible = monitors.iter().any(|monitor| {
+        let (width, height) = monitor.overlap(&state);
+        width >= MIN_VISIBLE_EDGE && height >= MIN_VISIBLE_EDGE
+    });
+    if visible {
+        return state;
+    }
+
+    let width = i64::from(state.width).clamp(1, fallback.width.max(1));
+    let height = i64::from(state.height).clamp(1, fallback.height.max(1));
+    let x = i64::from(state.x).clamp(fallback.x, fallback.x + fallback.width - width);
+    let y = i64::from(state.y).clamp(fallback.y, fallback.y + fallback.height - height);
+
+    state.width = width as u32;
+    state.height = height as u32;
+    state.x = x as i32;
+    state.y = y as i32;
+    state
+}
+
+fn capture_normal_state<R: tauri::Runtime>(window: &tauri::Window<R>) -> Option<WindowState> {
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    if size.width == 0 || size.height == 0 {
+        return None;
+    }
+
+    Some(WindowState {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        maximized: false,
+    })
+}
+
+fn save_window_state<R: tauri::Runtime>(window: &tauri::Window<R>) {
+    if window.is_minimized().unwrap_or(false) {
+        return;
+    }
+
+    let tracker = window.state::<WindowStateTracker>();
+    let maximized = window.is_maximized().unwrap_or(false);
+    let mut last_normal = tracker
+        .last_normal
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if !maximized {
+        if let Some(current) = capture_normal_state(window) {
+            *last_normal = Some(current);
+        }
+    }
+
+    let Some(mut state) = *last_normal else {
+        return;
+    };
+    state.maximized = maximized;
+    drop(last_normal);
+
+    if let Err(error) = config_store::save_json(window.app_handle(), WINDOW_STATE_FILE, &state) {
+        log::warn!("failed to save window state: {}", error);
+    }
+}
+
+// One worker per app: each Moved/Resized event restarts its quiet period, and the state is written
+// once events stop for SAVE_DEBOUNCE.
+fn spawn_saver<R: tauri::Runtime>(window: tauri::Window<R>) -> mpsc::Sender<()> {
+    let (sender, events) = mpsc::channel::<()>();
+    std::thread::spawn(move || {
+        while events.recv().is_ok() {
+            loop {
+                match events.recv_timeout(SAVE_DEBOUNCE) {
+                    Ok(()) => continue,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+            if window
+                .state::<WindowStateTracker>()
+                .pending
+                .swap(false, Ordering::SeqCst)
+            {
+                save_window_state(&window);
+            }
+        }
+    });
+    sender
+}
+
+fn schedule_save<R: tauri::Runtime>(window: &tauri::Window<R>) {
+    let tracker = window.state::<WindowStateTracker>();
+    tracker.pending.store(true, Ordering::SeqCst);
+    let mut saver = tracker
+        .saver
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let sender = saver.get_or_insert_with(|| spawn_saver(window.clone()));
+    if sender.send(()).is_err() {
+        *saver = None;
+        log::warn!("window state saver stopped; saving immediately");
+        drop(saver);
+        save_now(window);
+    }
+}
+
+// Clearing `pending` keeps a debounced save from running after this one, when the window may
+// already be gone.
+fn save_now<R: tauri::Runtime>(window: &tauri::Window<R>) {
+    window
+        .state::<WindowStateTracker>()
+        .pending
+        .store(false, Ordering::SeqCst);
+    save_window_state(window);
+}
+
+pub fn flush_window_state<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+        save_now(&window.as_ref().window());
+    }
+}
+
+pub fn restore_window_state<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) else {
+        return;
+    };
+    let Some(saved) = config_store::load_json::<R, WindowState>(app, WINDOW_STATE_FILE) else {
+        return;
+    };
+    if saved.width == 0 || saved.height == 0 {
+        return;
+    }
+
+    let monitors = window
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(MonitorBounds::from_monitor)
+        .collect::<Vec<MonitorBounds>>();
+    let primary = window
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .map(|monitor| MonitorBounds::from_monitor(&monitor));
+    let state = clamp_to_monitors(saved, &monitors, primary);
+
+    let tracker = app.state::<WindowStateTracker>();
+    *tracker
+        .last_normal
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(state);
+
+    if let Err(error) = window.set_size(PhysicalSize::new(state.width, state.height)) {
+        log::warn!("failed to restore window size: {}", error);
+    }
+    if let Err(error) = window.set_position(PhysicalPosition::new(state.x, state.y)) {
+        log::warn!("failed to restore window position: {}", error);
+    }
+    if state.maximized {
+        if let Err(error) = window.maximize() {
+            log::warn!("failed to restore maximized window: {}", error);
+        }
+    }
+}
+
+pub fn handle_window_event<R: tauri::Runtime>(
+    window: &tauri::Window<R>,
+    event: &tauri::WindowEvent,
+) {
+    if window.label() != MAIN_WINDOW_LABEL {
+        return;
+    }
+
+    match event {
+        tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => schedule_save(window),
+        tauri::WindowEvent::CloseRequested { .. } => save_now(window),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONITOR: MonitorBounds = MonitorBounds {
+        x: 0,
+        y: 0,
+        width: 1920,
+        height: 1080,
+    };
+
+    fn window(x: i32, y: i32, width: u32, height: u32) -> WindowState {
+        WindowState {
+            x,
+            y,
+            width,
+            height,
+            maximized: false,
+        }
+    }
+
+    fn bounds(state: WindowState) -> (i32, i32, u32, u32) {
+        (state.x, state.y, state.width, state.height)
+    }
+
+    #[test]
+    fn visible_windows_are_left_alone() {
+        let state = window(100, 100, 800, 600);
+        assert_eq!(
+            bounds(clamp_to_monitors(state, &[MONITOR], None)),
+            (100, 100, 800, 600)
+        );
+    }
+
+    #[test]
+    fn off_screen_origin_moves_back_onto_the_primary_monitor() {
+        let secondary = MonitorBounds {
+            x: 1920,
+            y: 0,
+            width: 1280,
+            height: 1024,
+        };
+        // Saved on a monitor to the left that is no longer connected.
+        let state = window(-1500, 200, 800, 600);
+        assert_eq!(
+            bounds(clamp_to_monitors(
+                state,
+                &[MONITOR, secondary],
+                Some(MONITOR)
+            )),
+            (0, 200, 800, 600)
+        );
+
+        let state = window(5000, 3000, 800, 600);
+        assert_eq!(
+            bounds(clamp_to_monitors(state, &[MONITOR], None)),
+            (1120, 480, 800, 600)
+        );
+    }
+
+    #[test]
+    fn barely_visible_windows_count_as_off_screen() {
+        let state = window(1900, 100, 800, 600);
+        assert_eq!(
+            bounds(clamp_to_monitors(state, &[MONITOR], None)),
+            (1120, 100, 800, 600)
+        );
+    }
+
+    #[test]
+    fn windows_larger_than_the_monitor_shrink_to_fit() {
+        let state = window(-4000, -4000, 3840, 2160);
+        assert_eq!(
+            bounds(clamp_to_monitors(state, &[MONITOR], Some(MONITOR))),
+            (0, 0, 1920, 1080)
+        );
+    }
+
+    #[test]
+    fn without_monitors_the_saved_state_is_kept() {
+        let state = window(-4000, -4000, 800, 600);
+        assert_eq!(
+            bounds(clamp_to_monitors(state, &[], None)),
+            (-4000, -4000, 800, 600)
+        );
+    }
+}
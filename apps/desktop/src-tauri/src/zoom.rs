@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::Manager;
+
+use crate::config_store;
+use crate::window_state::MAIN_WINDOW_LABEL;
+
+const ZOOM_FILE: &str = "zoom.json";
+const MIN_ZOOM: f64 = 0.5;
+const MAX_ZOOM: f64 = 3.0;
+
+#[derive(Serialize, Deserialize)]
+struct StoredZoom {
+    factor: f64,
+}
+
+pub struct ZoomState(Mutex<f64>);
+
+impl Default for ZoomState {
+    fn default() -> Self {
+        Self(Mutex::new(1.0))
+    }
+}
+
+fn clamp_zoom(factor: f64) -> Result<f64, String> {
+    if !factor.is_finite() {
+        return Err(format!("invalid zoom factor: {}", factor));
+    }
+    Ok(factor.clamp(MIN_ZOOM, MAX_ZOOM))
+}
+
+pub fn restore_zoom<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let Some(stored) = config_store::load_json::<R, StoredZoom>(app, ZOOM_FILE) else {
+        return;
+    };
+    let Ok(factor) = clamp_zoom(stored.factor) else {
+        return;
+    };
+
+    *app.state::<ZoomState>()
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = factor;
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+        if let Err(error) = window.set_zoom(factor) {
+            log::warn!("failed to restore zoom: {}", error);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn set_zoom(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    factor: f64,
+) -> Result<f64, String> {
+    let factor = clamp_zoom(factor)?;
+    window
+        .set_zoom(factor)
+        .map_err(|error| format!("failed to set zoom: {}", error))?;
+
+    *app.state::<ZoomState>()
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = factor;
+    config_store::save_json(&app, ZOOM_FILE, &StoredZoom { factor })?;
+    Ok(factor)
+}
+
+#[tauri::command]
+pub fn get_zoom(state: tauri::State<'_, ZoomState>) -> f64 {
+    *state
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
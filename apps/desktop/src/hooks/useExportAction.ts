@@ -1,6 +1,6 @@
 import { useCallback } from 'react';
 
-import { runPipeline, isTauriRuntime } from '../lib/desktop-runtime';
+import { checkDiskSpace, runPipeline, isTauriRuntime } from '../lib/desktop-runtime';
 import {
   collectExpectedRenderSuffixes,
   findMissingRenderedFiles,
@@ -11,6 +11,23 @@ import { parseJsonOrNull } from '../lib/json-utils';
 import type { ProjectDoc } from '../lib/project-model';
 import type { BusyHelpers, BusyRunOptions } from './useBusyRunner';
 
+// Generous per-image estimate; each render is written to the preview dir and again on export.
+const ESTIMATED_BYTES_PER_RENDER = 10 * 1024 * 1024;
+
+async function hasEnoughDiskSpace(outputDir: string, renderCount: number) {
+  const requiredBytes = renderCount * ESTIMATED_BYTES_PER_RENDER * 2;
+  const space = await checkDiskSpace(outputDir).catch(() => null);
+  if (!space || space.available_bytes >= requiredBytes) {
+    return true;
+  }
+
+  const availableMb = Math.floor(space.available_bytes / (1024 * 1024));
+  const requiredMb = Math.ceil(requiredBytes / (1024 * 1024));
+  return typeof window === 'undefined' || typeof window.confirm !== 'function'
+    ? true
+    : window.confirm(`Only ${availableMb} MB free for an export that may need about ${requiredMb} MB. Export anyway?`);
+}
+
 interface UseExportActionArgs {
   doc: ProjectDoc;
   outputDir: string;
@@ -64,6 +81,12 @@ export function useExportAction({
           throw new Error('No export targets found. Check slots/locales/devices/platforms.');
         }
 
+        setDetail('Checking free disk space...');
+        if (!(await hasEnoughDiskSpace(resolvedOutputDir, expectedSuffixes.length))) {
+          setExportStatus('Export cancelled.');
+          return;
+        }
+
         await renderExportImagesFromSnapshot({
           snapshot,
           targetDir: previewRenderDir,
@@ -78,8 +101,8 @@ export function useExportAction({
         }
 
         setDetail('Exporting preview renders...');
-        const exportRaw = await runPipeline('export', [projectPath, previewRenderDir, resolvedOutputDir, ...flags]);
-        const exportParsed = parseJsonOrNull(exportRaw) as {
+        const exportResult = await runPipeline('export', [projectPath, previewRenderDir, resolvedOutputDir, ...flags]);
+        const exportParsed = parseJsonOrNull(exportResult.stdout) as {
           outputDir?: string;
           zipPath?: string | null;
           metadataCsvPath?: string | null;
@@ -34,7 +34,7 @@ export function usePersistProjectSnapshot({
       syncTemplateMain: options?.syncTemplateMain !== false,
       slotWidth: Math.max(1, selectedDeviceWidth || TEMPLATE_REFERENCE_WIDTH)
     });
-    await writeTextFile(projectPath, JSON.stringify(next, null, 2));
+    await writeTextFile(projectPath, JSON.stringify(next, null, 2), 'lf');
     setSavedProjectSignature(serializeProjectSignature(next));
     return next;
   }, [
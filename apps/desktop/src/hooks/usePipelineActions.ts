@@ -120,8 +120,8 @@ export function usePipelineActions({
       setDetail('Saving project config...');
       await persistProjectSnapshot();
       setDetail('Checking project rules...');
-      const output = await runPipeline('validate', [projectPath]);
-      const parsed = parseJsonOrNull(output) as { issues?: ValidateIssue[] } | null;
+      const { stdout } = await runPipeline('validate', [projectPath]);
+      const parsed = parseJsonOrNull(stdout) as { issues?: ValidateIssue[] } | null;
       setIssues(parsed?.issues || []);
     }, {
       action: 'validate',
@@ -1,6 +1,13 @@
 import { useCallback } from 'react';
 
-import { isTauriRuntime, pickProjectFile, pickProjectSavePath, readTextFile, writeTextFile } from '../lib/desktop-runtime';
+import {
+  addRecentProject,
+  isTauriRuntime,
+  pickProjectFile,
+  pickProjectSavePath,
+  readTextFile,
+  writeTextFile
+} from '../lib/desktop-runtime';
 import { parseJsonOrNull } from '../lib/json-utils';
 import {
   type ProjectDoc,
@@ -76,6 +83,7 @@ export function useProjectFileActions({
         setOutputDir(resolvedLoadedOutputDir);
         setProjectPath(pickedPath);
         setSavedProjectSignature(serializeProjectSignature(loadedSnapshot));
+        await addRecentProject(pickedPath).catch(() => undefined);
       }, {
         action: 'load-project',
         title: 'Loading Project',
@@ -125,7 +133,7 @@ export function useProjectFileActions({
           syncTemplateMain: true,
           slotWidth: TEMPLATE_REFERENCE_WIDTH
         });
-        await writeTextFile(targetPath, JSON.stringify(next, null, 2));
+        await writeTextFile(targetPath, JSON.stringify(next, null, 2), 'lf');
         setProjectPath(targetPath);
         setSavedProjectSignature(serializeProjectSignature(next));
       }, {
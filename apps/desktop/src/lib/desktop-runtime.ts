@@ -1,41 +1,134 @@
-import { invoke as tauriInvoke } from '@tauri-apps/api/core';
+import { convertFileSrc, invoke as tauriInvoke } from '@tauri-apps/api/core';
 
-export async function runPipeline(command: string, args: string[]) {
-  return invokeCommand<string>('run_pipeline', { command, args });
+export interface ListedFile {
+  path: string;
+  id: string;
+}
+
+export interface PipelineError {
+  kind: 'runtime_missing' | 'script_missing' | 'spawn_failed' | 'pipeline_failed' | 'cancelled' | 'timeout' | 'internal';
+  message: string;
+  hint: string | null;
+  output: PipelineOutput | null;
+}
+
+export interface PipelineOutput {
+  exit_code: number | null;
+  stdout: string;
+  stderr: string;
+  duration_ms: number;
+  command_line: string[];
+}
+
+export interface RuntimeInfo {
+  node_cmd: string;
+  version: string;
+}
+
+function isPipelineError(error: unknown): error is PipelineError {
+  return typeof error === 'object' && error !== null && 'kind' in error && 'message' in error;
+}
+
+function toPipelineFailure(error: unknown) {
+  if (!isPipelineError(error)) return error;
+  const failure = new Error(error.hint ? `${error.message}\n${error.hint}` : error.message);
+  return Object.assign(failure, { kind: error.kind, hint: error.hint, output: error.output });
+}
+
+export interface RunPipelineOptions {
+  progressEvents?: boolean;
+  jobId?: string;
+  timeoutSecs?: number;
+}
+
+export async function runPipeline(command: string, args: string[], options: RunPipelineOptions = {}) {
+  return invokeCommand<PipelineOutput>('run_pipeline', { command, args, ...options }).catch((error: unknown) => {
+    throw toPipelineFailure(error);
+  });
+}
+
+export async function checkRuntime() {
+  return invokeCommand<RuntimeInfo>('check_runtime', {}).catch((error: unknown) => {
+    throw toPipelineFailure(error);
+  });
 }
 
 export async function readTextFile(path: string) {
   return invokeCommand<string>('read_text_file', { path });
 }
 
-export async function writeTextFile(path: string, content: string) {
-  return invokeCommand<void>('write_text_file', { path, content });
+export async function addRecentProject(path: string) {
+  return invokeCommand<void>('add_recent_project', { path });
+}
+
+export async function writeTextFile(path: string, content: string, lineEnding?: 'lf' | 'crlf', keepBackup?: boolean) {
+  return invokeCommand<void>('write_text_file', { path, content, lineEnding, keepBackup });
+}
+
+export async function readJson<T = unknown>(path: string) {
+  return invokeCommand<T>('read_json', { path });
+}
+
+export async function writeJson(path: string, value: unknown, pretty = true) {
+  return invokeCommand<void>('write_json', { path, value, pretty });
+}
+
+export async function toProjectRelative(path: string) {
+  return invokeCommand<string | null>('to_project_relative', { path });
+}
+
+export async function toAbsolute(relative: string) {
+  return invokeCommand<string>('to_absolute', { relative });
+}
+
+export async function listPngFiles(path: string, options?: { maxDepth?: number; exclude?: string[] }) {
+  return invokeCommand<ListedFile[]>('list_png_files', { path, maxDepth: options?.maxDepth, exclude: options?.exclude });
+}
+
+export async function scanPngFiles(path: string) {
+  return invokeCommand<number>('scan_png_files', { path });
 }
 
-export async function listPngFiles(path: string) {
-  return invokeCommand<string[]>('list_png_files', { path });
+export async function cancelScan(scanId: number) {
+  return invokeCommand<boolean>('cancel_scan', { scanId });
 }
 
-export async function readFileBase64(path: string) {
-  return invokeCommand<string>('read_file_base64', { path });
+export async function readFileBase64(path: string, fileId?: string) {
+  return invokeCommand<string>('read_file_base64', { path, fileId });
 }
 
 export async function listSystemFonts() {
   return invokeCommand<string[]>('list_system_fonts', {});
 }
 
-export async function writeFileBase64(path: string, dataBase64: string) {
-  return invokeCommand<void>('write_file_base64', { path, dataBase64 });
+export async function writeFileBase64(path: string, dataBase64: string, keepBackup?: boolean) {
+  return invokeCommand<void>('write_file_base64', { path, dataBase64, keepBackup });
 }
 
 export async function getDefaultExportDir() {
-  return invokeCommand<string | null>('get_default_export_dir', {});
+  return invokeCommand<string>('get_default_export_dir', {});
+}
+
+export async function setDefaultExportDir(path: string | null) {
+  return invokeCommand<void>('set_default_export_dir', { path });
 }
 
 export async function pickOutputDir() {
   return invokeCommand<string | null>('pick_output_dir', {});
 }
 
+export async function canWriteDir(path: string) {
+  return invokeCommand<{ writable: boolean; reason: string | null }>('can_write_dir', { path });
+}
+
+export async function directorySize(path: string) {
+  return invokeCommand<{ total_bytes: number; file_count: number }>('directory_size', { path });
+}
+
+export async function checkDiskSpace(path: string) {
+  return invokeCommand<{ available_bytes: number; total_bytes: number }>('check_disk_space', { path });
+}
+
 export async function pickProjectFile(preferredDir?: string) {
   return invokeCommand<string | null>('pick_project_file', { preferredDir });
 }
@@ -61,6 +154,11 @@ export function browserFileToBase64(file: File) {
   });
 }
 
+// Serves project and export files straight from disk via the `dma-asset` scheme, with range support.
+export function toAssetUrl(path: string) {
+  return convertFileSrc(path, 'dma-asset');
+}
+
 export function isTauriRuntime() {
   return typeof window !== 'undefined'
     && typeof (window as { __TAURI_INTERNALS__?: { invoke?: unknown } }).__TAURI_INTERNALS__?.invoke === 'function';
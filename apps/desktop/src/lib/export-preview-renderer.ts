@@ -121,6 +121,6 @@ export function collectExpectedRenderSuffixes(snapshot: ProjectDoc) {
 
 export async function findMissingRenderedFiles(baseDir: string, expectedSuffixes: string[]) {
   const files = await listPngFiles(baseDir);
-  const missing = expectedSuffixes.filter((suffix) => !files.some((entry) => entry.endsWith(suffix)));
+  const missing = expectedSuffixes.filter((suffix) => !files.some((entry) => entry.path.endsWith(suffix)));
   return { files, missing };
 }
@@ -33,12 +33,12 @@ export async function loadSlotPreviewMapFromDir({
 
   for (const slot of sortedSlots) {
     const suffix = `${selectedPlatform}/${selectedDevice}/${selectedLocale}/${slot.id}.png`;
-    const picked = files.find((entry) => entry.endsWith(suffix));
+    const picked = files.find((entry) => entry.path.endsWith(suffix));
     if (!picked) continue;
 
-    const base64 = await readFileBase64(picked);
+    const base64 = await readFileBase64(picked.path, picked.id);
     urls[slot.id] = `data:image/png;base64,${base64}`;
-    paths[slot.id] = picked;
+    paths[slot.id] = picked.path;
   }
 
   return {
@@ -68,12 +68,12 @@ export async function loadPreviewMatrixFromDir({
 
     for (const slot of sortedSlots) {
       const suffix = `${selectedPlatform}/${selectedDevice}/${locale}/${slot.id}.png`;
-      const picked = files.find((entry) => entry.endsWith(suffix));
+      const picked = files.find((entry) => entry.path.endsWith(suffix));
       if (!picked) continue;
 
-      const base64 = await readFileBase64(picked);
+      const base64 = await readFileBase64(picked.path, picked.id);
       urlsByLocale[locale][slot.id] = `data:image/png;base64,${base64}`;
-      pathsByLocale[locale][slot.id] = picked;
+      pathsByLocale[locale][slot.id] = picked.path;
     }
   }
 
@@ -15,6 +15,13 @@ function usage() {
   node scripts/pipeline.js all <projectPath> [workDir]`);
 }
 
+// The desktop app sets DMA_PIPELINE_PROGRESS and turns these lines into progress events.
+function reportProgress(pct, step) {
+  if (process.env.DMA_PIPELINE_PROGRESS) {
+    console.log(`##PROGRESS ${JSON.stringify({ pct, step })}`);
+  }
+}
+
 function parseValueFlag(flags, name) {
   const prefix = `${name}=`;
   const found = flags.find((flag) => flag.startsWith(prefix));
@@ -47,7 +54,9 @@ async function run() {
     }
 
     const outputDir = renderDir || path.join(path.dirname(projectPath), 'dist-render');
+    reportProgress(0, 'rendering');
     const result = await renderProject(projectPath, { outputDir, preferPlaywright: true });
+    reportProgress(100, 'done');
     console.log(JSON.stringify({ action, ...result, outputDir }, null, 2));
     return;
   }
@@ -99,6 +108,7 @@ async function run() {
       process.exit(1);
     }
 
+    reportProgress(0, 'exporting');
     const result = await exportProject(projectPath, {
       renderDir,
       outputDir,
@@ -106,6 +116,7 @@ async function run() {
       fastlaneLayout: flags.includes('--fastlane'),
       metadataCsv: flags.includes('--metadata-csv')
     });
+    reportProgress(100, 'done');
 
     console.log(JSON.stringify({ action, ...result }, null, 2));
     return;
@@ -122,13 +133,16 @@ async function run() {
     const renderDir = `${base}-render`;
     const outputDir = base;
 
+    reportProgress(0, 'rendering');
     const renderResult = await renderProject(projectPath, { outputDir: renderDir, preferPlaywright: true });
+    reportProgress(50, 'exporting');
     const exportResult = await exportProject(projectPath, {
       renderDir,
       outputDir,
       zip: true,
       fastlaneLayout: false
     });
+    reportProgress(100, 'done');
 
     console.log(JSON.stringify({ action, renderResult, exportResult }, null, 2));
     return;